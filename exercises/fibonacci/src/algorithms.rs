@@ -0,0 +1,219 @@
+// Five ways to compute the same number, from hopeless to very fast. Each is a
+// FibonacciAlgorithm, so they can be swapped for one another, compared and
+// timed (see src/bench.rs):
+//
+// - Recursive is the exercise's solution. F(n) calls F(n - 1) and F(n - 2),
+//   which call F(n - 2) and F(n - 3) twice between them, and so on. The number
+//   of calls grows like F(n) itself, exponentially.
+// - Memoized is the same recursion, but remembers each F(i) the first time
+//   it's computed, so each one is only computed once: O(n).
+// - Iterative walks up from F(0) and F(1), keeping only the last two: also
+//   O(n), without the recursion or the memory.
+// - Matrix uses the fact that [[1, 1], [1, 0]] to the nth power is
+//   [[F(n + 1), F(n)], [F(n), F(n - 1)]], and computes that power by repeated
+//   squaring: O(log n) matrix multiplications.
+// - FastDoubling gets F(2k) and F(2k + 1) straight from F(k) and F(k + 1),
+//   halving n at each step: O(log n) too, with less work per step.
+//
+// They all return a u64, so they only go up to F(93) (see checked.rs).
+// Matrix and FastDoubling work with u128s inside, because on the way to F(n)
+// they also compute somewhat bigger numbers, like F(n + 1).
+
+use crate::checked::{OverflowError, MAX_N_U64};
+
+pub trait FibonacciAlgorithm {
+    fn name(&self) -> &'static str;
+
+    // F(n), for n <= MAX_N_U64
+    fn compute(&self, n: u32) -> u64;
+
+    fn fibonacci(&self, n: u32) -> Result<u64, OverflowError> {
+        if n > MAX_N_U64 {
+            return Err(OverflowError {
+                n,
+                bits: 64,
+                max_n: MAX_N_U64,
+            });
+        }
+        Ok(self.compute(n))
+    }
+}
+
+// Past this Recursive takes too long to be worth waiting for: F(35) already
+// takes around 30 million calls.
+pub const RECURSIVE_MAX_N: u32 = 35;
+
+pub struct Recursive;
+pub struct Memoized;
+pub struct Iterative;
+pub struct Matrix;
+pub struct FastDoubling;
+
+// Every algorithm, slowest first
+pub const ALL: &[&dyn FibonacciAlgorithm] =
+    &[&Recursive, &Memoized, &Iterative, &Matrix, &FastDoubling];
+
+pub fn by_name(name: &str) -> Option<&'static dyn FibonacciAlgorithm> {
+    ALL.iter().copied().find(|a| a.name() == name)
+}
+
+impl FibonacciAlgorithm for Recursive {
+    fn name(&self) -> &'static str {
+        "recursive"
+    }
+
+    fn compute(&self, n: u32) -> u64 {
+        if n < 2 {
+            n as u64
+        } else {
+            self.compute(n - 1) + self.compute(n - 2)
+        }
+    }
+}
+
+impl FibonacciAlgorithm for Memoized {
+    fn name(&self) -> &'static str {
+        "memoized"
+    }
+
+    fn compute(&self, n: u32) -> u64 {
+        fn go(n: usize, memo: &mut [Option<u64>]) -> u64 {
+            if let Some(f) = memo[n] {
+                return f;
+            }
+            let f = if n < 2 {
+                n as u64
+            } else {
+                go(n - 1, memo) + go(n - 2, memo)
+            };
+            memo[n] = Some(f);
+            f
+        }
+        let n = n as usize;
+        go(n, &mut vec![None; n + 1])
+    }
+}
+
+impl FibonacciAlgorithm for Iterative {
+    fn name(&self) -> &'static str {
+        "iterative"
+    }
+
+    fn compute(&self, n: u32) -> u64 {
+        if n == 0 {
+            return 0;
+        }
+        // F(i - 1) and F(i), starting at i = 1. Stopping at F(n) rather than
+        // F(n + 1) means F(93) doesn't overflow on the way.
+        let (mut previous, mut current) = (0u64, 1u64);
+        for _ in 1..n {
+            (previous, current) = (current, previous + current);
+        }
+        current
+    }
+}
+
+type Matrix2 = [[u128; 2]; 2];
+
+fn multiply(a: &Matrix2, b: &Matrix2) -> Matrix2 {
+    let mut product = [[0; 2]; 2];
+    for (i, row) in product.iter_mut().enumerate() {
+        for (j, cell) in row.iter_mut().enumerate() {
+            *cell = a[i][0] * b[0][j] + a[i][1] * b[1][j];
+        }
+    }
+    product
+}
+
+impl FibonacciAlgorithm for Matrix {
+    fn name(&self) -> &'static str {
+        "matrix"
+    }
+
+    fn compute(&self, n: u32) -> u64 {
+        let mut power = [[1, 0], [0, 1]];
+        let mut square = [[1, 1], [1, 0]];
+        let mut n = n;
+        while n > 0 {
+            if n & 1 == 1 {
+                power = multiply(&power, &square);
+            }
+            n >>= 1;
+            // Skipping the last squaring, which isn't needed, keeps the
+            // numbers small enough for a u128.
+            if n > 0 {
+                square = multiply(&square, &square);
+            }
+        }
+        power[0][1] as u64
+    }
+}
+
+// (F(n), F(n + 1))
+fn doubling(n: u32) -> (u128, u128) {
+    if n == 0 {
+        return (0, 1);
+    }
+    let (a, b) = doubling(n / 2);
+    // F(2k) = F(k) * (2 * F(k + 1) - F(k))
+    // F(2k + 1) = F(k)^2 + F(k + 1)^2
+    let even = a * (2 * b - a);
+    let odd = a * a + b * b;
+    if n & 1 == 0 {
+        (even, odd)
+    } else {
+        (odd, even + odd)
+    }
+}
+
+impl FibonacciAlgorithm for FastDoubling {
+    fn name(&self) -> &'static str {
+        "fast-doubling"
+    }
+
+    fn compute(&self, n: u32) -> u64 {
+        doubling(n).0 as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checked::fibonacci_u64;
+
+    #[test]
+    fn all_agree() {
+        for algorithm in ALL {
+            // The recursive one would take ages beyond this.
+            let max_n = if algorithm.name() == "recursive" {
+                30
+            } else {
+                MAX_N_U64
+            };
+            for n in 0..=max_n {
+                assert_eq!(
+                    algorithm.fibonacci(n),
+                    fibonacci_u64(n),
+                    "{} of {n}",
+                    algorithm.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn too_big() {
+        for algorithm in ALL {
+            assert_eq!(
+                algorithm.fibonacci(MAX_N_U64 + 1).unwrap_err().max_n,
+                MAX_N_U64
+            );
+        }
+    }
+
+    #[test]
+    fn find_by_name() {
+        assert_eq!(by_name("fast-doubling").unwrap().compute(10), 55);
+        assert!(by_name("guessing").is_none());
+    }
+}
@@ -0,0 +1,79 @@
+// Times each of the fibonacci algorithms over a range of n, to show how
+// differently O(2^n), O(n) and O(log n) grow. Run it in release mode, e.g.
+//
+//     cargo run --release -p fibonacci --bin fibonacci-bench
+//     cargo run --release -p fibonacci --bin fibonacci-bench -- 10 20 30
+//
+// Each cell is how long one call takes on average.
+
+use std::env;
+use std::hint::black_box;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use fib::algorithms::{FibonacciAlgorithm, ALL, RECURSIVE_MAX_N};
+use fib::checked::MAX_N_U64;
+
+const DEFAULT_NS: &[u32] = &[5, 10, 20, 30, 35, 50, 70, 93];
+
+// Keep calling until this much time has passed, so that quick calls get timed
+// over many runs.
+const MIN_TIME: Duration = Duration::from_millis(20);
+
+fn time(algorithm: &dyn FibonacciAlgorithm, n: u32) -> Duration {
+    let start = Instant::now();
+    let mut runs = 0;
+    while start.elapsed() < MIN_TIME {
+        black_box(algorithm.compute(black_box(n)));
+        runs += 1;
+    }
+    start.elapsed() / runs
+}
+
+fn format(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{nanos} ns")
+    } else if nanos < 1_000_000 {
+        format!("{:.1} µs", nanos as f64 / 1e3)
+    } else {
+        format!("{:.1} ms", nanos as f64 / 1e6)
+    }
+}
+
+fn main() -> ExitCode {
+    let mut ns = Vec::new();
+    for arg in env::args().skip(1) {
+        match arg.parse::<u32>() {
+            Ok(n) if n <= MAX_N_U64 => ns.push(n),
+            _ => {
+                eprintln!("error: `{arg}` isn't a number from 0 to {MAX_N_U64}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    if ns.is_empty() {
+        ns = DEFAULT_NS.to_vec();
+    }
+    if cfg!(debug_assertions) {
+        eprintln!("note: this is a debug build, use --release for realistic timings\n");
+    }
+
+    print!("{:>4}", "n");
+    for algorithm in ALL {
+        print!("{:>15}", algorithm.name());
+    }
+    println!();
+    for n in ns {
+        print!("{n:>4}");
+        for algorithm in ALL {
+            if algorithm.name() == "recursive" && n > RECURSIVE_MAX_N {
+                print!("{:>15}", "-");
+            } else {
+                print!("{:>15}", format(time(*algorithm, n)));
+            }
+        }
+        println!();
+    }
+    ExitCode::SUCCESS
+}
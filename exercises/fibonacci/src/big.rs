@@ -0,0 +1,180 @@
+// F(100) already has 21 digits, too many for a u64, and F(186) is as far as a
+// u128 goes. For anything bigger we need integers that grow as needed, which
+// std doesn't have, so here's a small one: just enough to add, multiply and
+// print the result.
+//
+// A BigUint is a list of "digits" in base 1,000,000,000, lowest first. Each
+// fits in a u32, the product of two fits in a u64, and printing one in decimal
+// is just printing each limb with nine digits.
+
+use std::fmt;
+use std::ops::{Add, Mul};
+
+const BASE: u64 = 1_000_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BigUint {
+    // Lowest first, and never with zeros at the end, so that each number has
+    // only one representation. Zero has no limbs at all.
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    fn trim(mut self) -> BigUint {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(mut n: u64) -> BigUint {
+        let mut limbs = Vec::new();
+        while n > 0 {
+            limbs.push((n % BASE) as u32);
+            n /= BASE;
+        }
+        BigUint { limbs }
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let mut limbs = Vec::with_capacity(self.limbs.len().max(other.limbs.len()) + 1);
+        let mut carry = 0;
+        for i in 0..self.limbs.len().max(other.limbs.len()) {
+            let sum = carry
+                + self.limbs.get(i).copied().unwrap_or(0) as u64
+                + other.limbs.get(i).copied().unwrap_or(0) as u64;
+            limbs.push((sum % BASE) as u32);
+            carry = sum / BASE;
+        }
+        if carry > 0 {
+            limbs.push(carry as u32);
+        }
+        BigUint { limbs }
+    }
+}
+
+// Long multiplication, like on paper: every limb of one times every limb of the
+// other.
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        if self.limbs.is_empty() || other.limbs.is_empty() {
+            return BigUint::default();
+        }
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                // At most (BASE - 1)^2 + 2 * (BASE - 1), well within a u64
+                let product = a as u64 * b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = (product % BASE) as u32;
+                carry = product / BASE;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+        BigUint { limbs }.trim()
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Some((last, rest)) = self.limbs.split_last() else {
+            return write!(f, "0");
+        };
+        // Only the leading limb goes without its leading zeros.
+        write!(f, "{last}")?;
+        for limb in rest.iter().rev() {
+            write!(f, "{limb:09}")?;
+        }
+        Ok(())
+    }
+}
+
+// F(n) exactly, however big, by fast doubling (see algorithms.rs). This version
+// works with F(k - 1) and F(k) rather than F(k) and F(k + 1), which needs no
+// subtraction:
+//
+//     F(2k - 1) = F(k - 1)^2 + F(k)^2
+//     F(2k)     = F(k) * (F(k) + 2 * F(k - 1))
+pub fn fibonacci(n: u64) -> BigUint {
+    // (F(n - 1), F(n)), where F(-1) is 1 so that F(1) = F(0) + F(-1)
+    fn pair(n: u64) -> (BigUint, BigUint) {
+        if n == 0 {
+            return (BigUint::from(1), BigUint::from(0));
+        }
+        let (a, b) = pair(n / 2);
+        let odd = &(&a * &a) + &(&b * &b);
+        let even = &b * &(&b + &(&a + &a));
+        if n & 1 == 0 {
+            (odd, even)
+        } else {
+            let next = &odd + &even;
+            (even, next)
+        }
+    }
+    pair(n).1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checked::{fibonacci_u128, MAX_N_U128};
+
+    #[test]
+    fn arithmetic() {
+        let big = |n: u64| BigUint::from(n);
+        assert_eq!(BigUint::default().to_string(), "0");
+        assert_eq!(big(0), BigUint::default());
+        assert_eq!((&big(999_999_999) + &big(1)).to_string(), "1000000000");
+        assert_eq!((&big(1_000_000_001) * &big(0)).to_string(), "0");
+        assert_eq!(
+            (&big(u64::MAX) * &big(u64::MAX)).to_string(),
+            (u64::MAX as u128 * u64::MAX as u128).to_string()
+        );
+
+        // 123456789012345678901234567890 and 987654321098765432109876543210
+        let e15 = big(10u64.pow(15));
+        let a = &(&big(123456789012345) * &e15) + &big(678901234567890);
+        let b = &(&big(987654321098765) * &e15) + &big(432109876543210);
+        assert_eq!(a.to_string(), "123456789012345678901234567890");
+        assert_eq!(
+            (&a * &b).to_string(),
+            "121932631137021795226185032733622923332237463801111263526900"
+        );
+    }
+
+    #[test]
+    fn agrees_with_u128() {
+        for n in 0..=MAX_N_U128 {
+            assert_eq!(
+                fibonacci(n as u64).to_string(),
+                fibonacci_u128(n).unwrap().to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn huge_numbers() {
+        assert_eq!(
+            fibonacci(1000).to_string(),
+            "43466557686937456435688527675040625802564660517371780402481729089536555417949051890403879840079255169295922593080322634775209689623239873322471161642996440906533187938298969649928516003704476137795166849228875"
+        );
+
+        let f = fibonacci(10_000).to_string();
+        assert_eq!(f.len(), 2090);
+        assert!(f.starts_with("33644764876431783266"));
+        assert!(f.ends_with("66073310059947366875"));
+
+        let f = fibonacci(100_000).to_string();
+        assert_eq!(f.len(), 20899);
+        assert!(f.starts_with("25974069347221724166"));
+        assert!(f.ends_with("49895374653428746875"));
+    }
+}
@@ -0,0 +1,116 @@
+// Fibonacci numbers grow fast, and a fixed-size integer runs out of room
+// sooner than you might think: F(48) doesn't fit in a u32. What happens then
+// depends on how the program was built. In a debug build, `a + b` panics with
+// "attempt to add with overflow". In a release build it silently wraps around,
+// and you get a wrong answer instead.
+//
+// Neither is great, so the functions here use `checked_add`, which returns
+// None instead of overflowing, and turn that into an error saying what went
+// wrong. (There's also `wrapping_add` when wrapping is what you want, and
+// `saturating_add` to stop at the maximum.)
+
+use std::error::Error;
+use std::fmt;
+
+// The largest n for which F(n) fits in each type
+pub const MAX_N_U32: u32 = 47;
+pub const MAX_N_U64: u32 = 93;
+pub const MAX_N_U128: u32 = 186;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverflowError {
+    pub n: u32,
+    // The size of the integer type in bits, e.g. 32 for u32
+    pub bits: u32,
+    pub max_n: u32,
+}
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "F({}) is too big for a u{}, the largest that fits is F({})",
+            self.n, self.bits, self.max_n
+        )
+    }
+}
+
+impl Error for OverflowError {}
+
+// The same function for each integer type. A macro saves writing it out three
+// times, since there's no trait in std for "has checked_add".
+macro_rules! checked_fibonacci {
+    ($name:ident, $int:ty, $max_n:expr) => {
+        pub fn $name(n: u32) -> Result<$int, OverflowError> {
+            let overflow = OverflowError {
+                n,
+                bits: <$int>::BITS,
+                max_n: $max_n,
+            };
+            if n == 0 {
+                return Ok(0);
+            }
+            // F(i - 1) and F(i), starting at i = 1
+            let (mut previous, mut current): ($int, $int) = (0, 1);
+            for _ in 1..n {
+                let next = previous.checked_add(current).ok_or(overflow)?;
+                previous = current;
+                current = next;
+            }
+            Ok(current)
+        }
+    };
+}
+
+checked_fibonacci!(fibonacci_u32, u32, MAX_N_U32);
+checked_fibonacci!(fibonacci_u64, u64, MAX_N_U64);
+checked_fibonacci!(fibonacci_u128, u128, MAX_N_U128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_numbers() {
+        let first: Vec<u32> = (0..10).map(|n| fibonacci_u32(n).unwrap()).collect();
+        assert_eq!(first, [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+        assert_eq!(fibonacci_u32(36), Ok(14930352));
+        assert_eq!(fibonacci_u64(36), Ok(14930352));
+        assert_eq!(fibonacci_u128(36), Ok(14930352));
+    }
+
+    #[test]
+    fn largest_that_fit() {
+        assert_eq!(fibonacci_u32(MAX_N_U32), Ok(2971215073));
+        assert_eq!(fibonacci_u64(MAX_N_U64), Ok(12200160415121876738));
+        assert_eq!(
+            fibonacci_u128(MAX_N_U128),
+            Ok(332825110087067562321196029789634457848)
+        );
+    }
+
+    #[test]
+    fn one_more_overflows() {
+        let err = fibonacci_u32(MAX_N_U32 + 1).unwrap_err();
+        assert_eq!(
+            err,
+            OverflowError {
+                n: 48,
+                bits: 32,
+                max_n: 47
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "F(48) is too big for a u32, the largest that fits is F(47)"
+        );
+        assert_eq!(fibonacci_u64(MAX_N_U64 + 1).unwrap_err().bits, 64);
+        assert_eq!(fibonacci_u128(MAX_N_U128 + 1).unwrap_err().bits, 128);
+        assert!(fibonacci_u32(u32::MAX).is_err());
+
+        // Which is what plain `+` would have got wrong: F(48) wraps around to
+        // F(48) - 2^32.
+        let wrapped = 1836311903u32.wrapping_add(2971215073);
+        assert_eq!(wrapped as u64, 4807526976 - (1 << 32));
+    }
+}
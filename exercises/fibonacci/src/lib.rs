@@ -0,0 +1,12 @@
+// More fibonacci, for after the exercise: the same numbers computed in ways
+// that hold up better than `fibonacci(n - 1) + fibonacci(n - 2)`. The exercise
+// itself is still src/main.rs. The library is called `fib` (see Cargo.toml), so
+// it's used like `fib::checked::fibonacci_u64(90)`.
+//
+// Its tests don't run with a plain `cargo test -p fibonacci`, which builds the
+// unsolved exercise too, so run them with `cargo test -p fibonacci --lib`.
+
+pub mod algorithms;
+pub mod big;
+pub mod checked;
+pub mod sequences;
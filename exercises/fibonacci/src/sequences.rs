@@ -0,0 +1,185 @@
+// Printing the first 10 fibonacci numbers with `for i in 0..10` and
+// `fibonacci(i)` computes each one from scratch, even though each is just the
+// sum of the two printed before it. An iterator can remember those two
+// instead, and hand out the numbers one after the other:
+//
+//     for f in Fibonacci::new().take(10) {
+//         println!("{f}");
+//     }
+//
+// And being an iterator, it works with all of Iterator's adapters, like
+// `Fibonacci::new().skip_while(|&f| f < 1000).take(3)` for the first three
+// past 1000. It ends after F(93), the last one that fits in a u64, rather than
+// overflowing.
+//
+// Fibonacci is one of a family of sequences where each number is a fixed
+// combination of the few before it. LinearRecurrence is the general version,
+// which also gives the Lucas numbers, tribonacci and Pell numbers.
+
+use std::collections::VecDeque;
+
+pub struct Fibonacci {
+    // The next two numbers to hand out. The second becomes None once it's too
+    // big for a u64, and then the first does too.
+    current: Option<u64>,
+    next: Option<u64>,
+}
+
+impl Fibonacci {
+    pub fn new() -> Fibonacci {
+        Fibonacci {
+            current: Some(0),
+            next: Some(1),
+        }
+    }
+}
+
+impl Default for Fibonacci {
+    fn default() -> Fibonacci {
+        Fibonacci::new()
+    }
+}
+
+impl Iterator for Fibonacci {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let current = self.current?;
+        self.current = self.next;
+        self.next = self.next.and_then(|next| next.checked_add(current));
+        Some(current)
+    }
+}
+
+// A sequence where each number is a fixed weighted sum of the ones before it:
+//
+//     a(n) = c[0] * a(n - k) + c[1] * a(n - k + 1) + ... + c[k - 1] * a(n - 1)
+//
+// starting from k given numbers. Fibonacci is c = [1, 1] from [0, 1]. Like
+// Fibonacci, it ends with the last number that fits in a u64.
+pub struct LinearRecurrence {
+    coefficients: Vec<u64>,
+    // The numbers still to hand out, oldest first
+    window: VecDeque<u64>,
+    // Whether the next number overflowed, after which no more are computed
+    overflowed: bool,
+}
+
+impl LinearRecurrence {
+    pub fn new(coefficients: &[u64], initial: &[u64]) -> LinearRecurrence {
+        assert_eq!(
+            coefficients.len(),
+            initial.len(),
+            "a recurrence needs as many starting numbers as coefficients"
+        );
+        LinearRecurrence {
+            coefficients: coefficients.to_vec(),
+            window: initial.iter().copied().collect(),
+            overflowed: false,
+        }
+    }
+
+    // 0, 1, 1, 2, 3, 5, 8, ...
+    pub fn fibonacci() -> LinearRecurrence {
+        LinearRecurrence::new(&[1, 1], &[0, 1])
+    }
+
+    // 2, 1, 3, 4, 7, 11, 18, ...: the same rule as fibonacci, starting
+    // elsewhere
+    pub fn lucas() -> LinearRecurrence {
+        LinearRecurrence::new(&[1, 1], &[2, 1])
+    }
+
+    // 0, 0, 1, 1, 2, 4, 7, 13, ...: the sum of the previous three
+    pub fn tribonacci() -> LinearRecurrence {
+        LinearRecurrence::new(&[1, 1, 1], &[0, 0, 1])
+    }
+
+    // 0, 1, 2, 5, 12, 29, ...: twice the previous one plus the one before
+    pub fn pell() -> LinearRecurrence {
+        LinearRecurrence::new(&[1, 2], &[0, 1])
+    }
+
+    fn next_number(&self) -> Option<u64> {
+        self.coefficients
+            .iter()
+            .zip(&self.window)
+            .try_fold(0u64, |sum, (&c, &a)| sum.checked_add(c.checked_mul(a)?))
+    }
+}
+
+impl Iterator for LinearRecurrence {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if !self.overflowed && self.window.len() == self.coefficients.len() {
+            match self.next_number() {
+                Some(next) => self.window.push_back(next),
+                None => self.overflowed = true,
+            }
+        }
+        self.window.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checked::{fibonacci_u64, MAX_N_U64};
+
+    #[test]
+    fn fibonacci_iterator() {
+        let first: Vec<u64> = Fibonacci::new().take(10).collect();
+        assert_eq!(first, [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+
+        // Every one that fits in a u64, and then it stops.
+        assert_eq!(Fibonacci::new().count(), MAX_N_U64 as usize + 1);
+        for (n, f) in Fibonacci::new().enumerate() {
+            assert_eq!(Ok(f), fibonacci_u64(n as u32));
+        }
+
+        let past_1000: Vec<u64> = Fibonacci::new().skip_while(|&f| f < 1000).take(3).collect();
+        assert_eq!(past_1000, [1597, 2584, 4181]);
+    }
+
+    #[test]
+    fn recurrences() {
+        let first = |sequence: LinearRecurrence| sequence.take(10).collect::<Vec<_>>();
+        assert_eq!(
+            first(LinearRecurrence::lucas()),
+            [2, 1, 3, 4, 7, 11, 18, 29, 47, 76]
+        );
+        assert_eq!(
+            first(LinearRecurrence::tribonacci()),
+            [0, 0, 1, 1, 2, 4, 7, 13, 24, 44]
+        );
+        assert_eq!(
+            first(LinearRecurrence::pell()),
+            [0, 1, 2, 5, 12, 29, 70, 169, 408, 985]
+        );
+
+        assert!(LinearRecurrence::fibonacci().eq(Fibonacci::new()));
+        for sequence in [
+            LinearRecurrence::lucas(),
+            LinearRecurrence::tribonacci(),
+            LinearRecurrence::pell(),
+        ] {
+            let numbers: Vec<u64> = sequence.collect();
+            let last = *numbers.last().unwrap();
+            // It stops right before the first number that doesn't fit.
+            assert!(numbers.windows(2).skip(3).all(|w| w[0] < w[1]));
+            assert!(last > u64::MAX / 3, "stopped early at {last}");
+        }
+    }
+
+    #[test]
+    fn lucas_from_fibonacci() {
+        // L(n) = F(n - 1) + F(n + 1), lining the sequences up with zip
+        let fibonacci: Vec<u64> = Fibonacci::new().collect();
+        let from_fibonacci = fibonacci.iter().zip(&fibonacci[2..]).map(|(a, b)| a + b);
+        assert!(LinearRecurrence::lucas()
+            .skip(1)
+            .zip(from_fibonacci)
+            .all(|(lucas, sum)| lucas == sum));
+    }
+}
@@ -1,5 +1,8 @@
 
 // EXCERSISE: Write a function that returns the nth fibonacci number
+// @skeleton-begin
+// Past n = 47 this overflows, since F(48) doesn't fit in a u32. checked.rs has
+// versions that say so rather than panicking or wrapping around.
 fn fibonacci(n: u32) -> u32 {
     if n == 0 {
         0
@@ -9,6 +12,7 @@ fn fibonacci(n: u32) -> u32 {
         fibonacci(n - 1) + fibonacci(n - 2)
     }
 }
+// @skeleton-end
 
 fn main() {
     
@@ -16,7 +20,302 @@ fn main() {
     assert_eq!(fibonacci(36), 14930352);
 
     // EXERCISE! Print out the first 10 fibonacci numbers
-    for i in 0..10 {
-        println!("{}", fibonacci(i));
+    // @skeleton-begin
+    // @skeleton:
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(err) = cli::run(&args) {
+        eprintln!("error: {err}\n{}", cli::USAGE);
+        std::process::exit(2);
     }
+    // @skeleton-end
 }
+// @skeleton-begin
+
+// Past printing the first 10, the solution is a small command line program on
+// top of the fib library (src/lib.rs), for example
+//
+//     cargo run -p fibonacci --bin fibonacci-solution -- 90
+//     cargo run -p fibonacci --bin fibonacci-solution -- 0..=20 --format csv
+//     cargo run -p fibonacci --bin fibonacci-solution -- 1000..1010 --mod 1000000007
+//
+// With no arguments it still prints the first 10 one per line, since that's
+// what the exercise's output is graded against.
+mod cli {
+    use std::io::{self, BufWriter, Write};
+    use std::ops::Range;
+
+    use fib::algorithms::{self, FibonacciAlgorithm, Iterative, ALL, RECURSIVE_MAX_N};
+    use fib::checked;
+
+    pub const USAGE: &str = "\
+usage: fibonacci-solution [N | FROM..TO | FROM..=TO] [--algorithm NAME]
+                          [--format plain|csv|json] [--mod M]";
+
+    #[derive(Debug, PartialEq)]
+    pub enum Format {
+        Plain,
+        Csv,
+        Json,
+    }
+
+    pub struct Options {
+        // A u64 range, so that it can end after u32::MAX with `..=`
+        pub indices: Range<u64>,
+        pub algorithm: &'static dyn FibonacciAlgorithm,
+        pub format: Format,
+        pub modulus: Option<u64>,
+    }
+
+    fn index(arg: &str) -> Result<u32, String> {
+        arg.parse()
+            .map_err(|_| format!("`{arg}` isn't an index, those go from 0 to {}", u32::MAX))
+    }
+
+    // "7", "0..10" or "0..=10"
+    fn indices(arg: &str) -> Result<Range<u64>, String> {
+        let Some((from, to)) = arg.split_once("..") else {
+            let n = index(arg)? as u64;
+            return Ok(n..n + 1);
+        };
+        let from = index(from)? as u64;
+        let to = match to.strip_prefix('=') {
+            Some(to) => index(to)? as u64 + 1,
+            None => index(to)? as u64,
+        };
+        if to < from {
+            return Err(format!("the range `{arg}` goes backwards"));
+        }
+        Ok(from..to)
+    }
+
+    pub fn parse(args: &[String]) -> Result<Options, String> {
+        let mut range = None;
+        let mut algorithm = None;
+        let mut format = Format::Plain;
+        let mut modulus = None;
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("`{arg}` needs a value"));
+            match arg.as_str() {
+                "--algorithm" => {
+                    let name = value()?;
+                    algorithm = Some(algorithms::by_name(name).ok_or_else(|| {
+                        let names: Vec<&str> = ALL.iter().map(|a| a.name()).collect();
+                        format!(
+                            "there's no algorithm called `{name}`, the choices are {}",
+                            names.join(", ")
+                        )
+                    })?);
+                }
+                "--format" => {
+                    format = match value()?.as_str() {
+                        "plain" => Format::Plain,
+                        "csv" => Format::Csv,
+                        "json" => Format::Json,
+                        other => {
+                            return Err(format!(
+                                "unknown format `{other}`, the choices are plain, csv and json"
+                            ))
+                        }
+                    }
+                }
+                "--mod" => {
+                    let m = value()?;
+                    match m.parse::<u64>() {
+                        Ok(m) if m > 0 => modulus = Some(m),
+                        _ => return Err(format!("`--mod {m}` needs a whole number above 0")),
+                    }
+                }
+                flag if flag.starts_with("--") => return Err(format!("unknown option `{flag}`")),
+                arg if range.is_some() => {
+                    return Err(format!("`{arg}` is one index or range too many"))
+                }
+                arg => range = Some(indices(arg)?),
+            }
+        }
+
+        // Numbers modulo m are never too big, so they're always computed the
+        // same way rather than by one of the u64 algorithms.
+        if modulus.is_some() && algorithm.is_some() {
+            return Err("`--algorithm` can't be combined with `--mod`".to_string());
+        }
+        let indices = range.unwrap_or(0..10);
+        if let (Some(algorithm), Some(last)) = (algorithm, indices.clone().next_back()) {
+            if algorithm.name() == "recursive" && last > RECURSIVE_MAX_N as u64 {
+                return Err(format!(
+                    "`--algorithm recursive` would take too long for F({last}), it only goes up to F({RECURSIVE_MAX_N})"
+                ));
+            }
+        }
+        Ok(Options {
+            indices,
+            algorithm: algorithm.unwrap_or(&Iterative),
+            format,
+            modulus,
+        })
+    }
+
+    // F(n) mod m, for any n. This is the fast doubling of algorithms.rs, in u128
+    // so that the products of two numbers below m can't overflow.
+    pub fn fibonacci_mod(n: u32, m: u64) -> u64 {
+        let m = m as u128;
+        // F(k) and F(k + 1) mod m, where k is the bits of n seen so far
+        let (mut a, mut b) = (0, 1 % m);
+        for bit in (0..u32::BITS).rev() {
+            // F(2k) = F(k) * (2F(k + 1) - F(k)) and F(2k + 1) = F(k)² + F(k + 1)²
+            let c = a * ((2 * b + m - a) % m) % m;
+            let d = (a * a % m + b * b % m) % m;
+            (a, b) = if (n >> bit) & 1 == 0 {
+                (c, d)
+            } else {
+                (d, (c + d) % m)
+            };
+        }
+        a as u64
+    }
+
+    // Writes the rows as they're computed, so that a long range with `--mod`
+    // never has to fit in memory.
+    pub fn write(
+        out: &mut impl Write,
+        rows: impl Iterator<Item = (u64, u64)>,
+        format: &Format,
+    ) -> io::Result<()> {
+        match format {
+            Format::Plain => {
+                for (_, value) in rows {
+                    writeln!(out, "{value}")?;
+                }
+            }
+            Format::Csv => {
+                writeln!(out, "n,fibonacci")?;
+                for (n, value) in rows {
+                    writeln!(out, "{n},{value}")?;
+                }
+            }
+            Format::Json => {
+                let mut rows = rows.peekable();
+                if rows.peek().is_none() {
+                    return writeln!(out, "[]");
+                }
+                writeln!(out, "[")?;
+                while let Some((n, value)) = rows.next() {
+                    let comma = if rows.peek().is_some() { "," } else { "" };
+                    writeln!(out, "  {{\"n\": {n}, \"fibonacci\": {value}}}{comma}")?;
+                }
+                writeln!(out, "]")?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn run(args: &[String]) -> Result<(), String> {
+        if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+            println!("{USAGE}");
+            return Ok(());
+        }
+        let options = parse(args)?;
+        // Without `--mod`, check the last index fits before printing anything,
+        // so that one that's too big doesn't leave half a CSV or JSON behind.
+        if let (None, Some(last)) = (options.modulus, options.indices.clone().next_back()) {
+            checked::fibonacci_u64(last as u32)
+                .map_err(|err| format!("{err} (`--mod M` works for any index)"))?;
+        }
+
+        let rows = options.indices.clone().map(|n| {
+            let value = match options.modulus {
+                Some(m) => fibonacci_mod(n as u32, m),
+                None => options.algorithm.compute(n as u32),
+            };
+            (n, value)
+        });
+        let mut out = BufWriter::new(io::stdout().lock());
+        match write(&mut out, rows, &options.format).and_then(|()| out.flush()) {
+            // Like when piped into `head`, which stops reading
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+            result => result.map_err(|err| format!("failed to write the numbers: {err}")),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use fib::big;
+
+        fn args(line: &str) -> Vec<String> {
+            line.split_whitespace().map(String::from).collect()
+        }
+
+        #[test]
+        fn parse_args() {
+            let options = parse(&[]).unwrap();
+            assert_eq!(options.indices, 0..10);
+            assert_eq!(options.algorithm.name(), "iterative");
+            assert_eq!(options.format, Format::Plain);
+            assert_eq!(options.modulus, None);
+
+            assert_eq!(parse(&args("7")).unwrap().indices, 7..8);
+            assert_eq!(parse(&args("3..5")).unwrap().indices, 3..5);
+            assert_eq!(parse(&args("3..=5")).unwrap().indices, 3..6);
+            let options = parse(&args("--format json 0..=4294967295 --mod 10")).unwrap();
+            assert_eq!(options.indices, 0..1 << 32);
+            assert_eq!(options.format, Format::Json);
+            assert_eq!(options.modulus, Some(10));
+            let options = parse(&args("--algorithm matrix")).unwrap();
+            assert_eq!(options.algorithm.name(), "matrix");
+
+            let error = |line| parse(&args(line)).err().unwrap();
+            assert_eq!(error("5..3"), "the range `5..3` goes backwards");
+            assert_eq!(error("1 2"), "`2` is one index or range too many");
+            assert_eq!(error("--format"), "`--format` needs a value");
+            assert_eq!(error("--mod 0"), "`--mod 0` needs a whole number above 0");
+            assert_eq!(error("--verbose"), "unknown option `--verbose`");
+            assert_eq!(
+                error("--mod 7 --algorithm matrix"),
+                "`--algorithm` can't be combined with `--mod`"
+            );
+            assert!(error("-1").starts_with("`-1` isn't an index"));
+            assert!(error("--algorithm slow").ends_with("iterative, matrix, fast-doubling"));
+            assert_eq!(
+                error("30..=36 --algorithm recursive"),
+                "`--algorithm recursive` would take too long for F(36), it only goes up to F(35)"
+            );
+            assert!(parse(&args("35 --algorithm recursive")).is_ok());
+        }
+
+        #[test]
+        fn modulo() {
+            for n in 0..=93 {
+                let f = Iterative.fibonacci(n).unwrap();
+                for m in [1, 2, 10, 1_000_000_007, u64::MAX] {
+                    assert_eq!(fibonacci_mod(n, m), f % m, "F({n}) mod {m}");
+                }
+            }
+            // Way past what fits in a u64, checked against the exact number
+            let m = 1_000_000_007;
+            let digits = big::fibonacci(10_000).to_string();
+            let expected = digits
+                .bytes()
+                .fold(0, |r, d| (r * 10 + (d - b'0') as u64) % m);
+            assert_eq!(fibonacci_mod(10_000, m), expected);
+        }
+
+        #[test]
+        fn formats() {
+            let written = |rows: &[(u64, u64)], format| {
+                let mut out = Vec::new();
+                write(&mut out, rows.iter().copied(), &format).unwrap();
+                String::from_utf8(out).unwrap()
+            };
+            let rows = [(0, 0), (1, 1), (2, 1)];
+            assert_eq!(written(&rows, Format::Plain), "0\n1\n1\n");
+            assert_eq!(written(&rows, Format::Csv), "n,fibonacci\n0,0\n1,1\n2,1\n");
+            assert_eq!(
+                written(&rows, Format::Json),
+                "[\n  {\"n\": 0, \"fibonacci\": 0},\n  {\"n\": 1, \"fibonacci\": 1},\n  {\"n\": 2, \"fibonacci\": 1}\n]\n"
+            );
+            assert_eq!(written(&[], Format::Json), "[]\n");
+        }
+    }
+}
+// @skeleton-end
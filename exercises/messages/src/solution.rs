@@ -2,10 +2,13 @@ use std::fmt;
 
 #[derive(Debug)]
 enum Message {
+    // @skeleton-begin
+    // @skeleton: // Exercise! implement the Move, Echo, ChangeColor, and Quit variants
     Move { x: i32, y: i32 },
     Echo(String),
     ChangeColor(u8, u8, u8),
     Quit,
+    // @skeleton-end
 }
 
 impl Message {
@@ -19,10 +22,12 @@ impl fmt::Display for Message {
         match self {
             // Exercise! implement the pretty_call method
             // Note: use the write! macro instead of println!
+            // @skeleton-begin
             Message::Move { x, y } => write!(f, "Move to ({}, {})", x, y),
             Message::Echo(s) => write!(f, "Echo: {}", s),
             Message::ChangeColor(r, g, b) => write!(f, "Change color to ({}, {}, {})", r, g, b),
             Message::Quit => write!(f, "Quit"),
+            // @skeleton-end
         }
     }
 }
@@ -0,0 +1,33 @@
+
+#[derive(Debug)]
+struct Package {
+    sender_country: String,
+    recipient_country: String,
+    weight_in_grams: i32,
+}
+
+impl Package {
+    fn new(sender_country: String, recipient_country: String, weight_in_grams: i32) -> Package {
+        if weight_in_grams <= 0 {
+            panic!("Can not ship a weightless package.")
+        } else {
+            Package {
+                sender_country,
+                recipient_country,
+                weight_in_grams,
+            }
+        }
+    }
+
+    fn is_international(&self) -> ??? {
+        // Something goes here...
+    }
+
+    fn get_fees(&self, cents_per_gram: i32) -> ??? {
+        // Something goes here...
+    }
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
@@ -19,58 +19,25 @@ impl Package {
         }
     }
 
+    // @skeleton-begin
+    // @skeleton: fn is_international(&self) -> ??? {
+    // @skeleton:     // Something goes here...
+    // @skeleton: }
     fn is_international(&self) -> bool {
         self.sender_country != self.recipient_country
     }
+    // @skeleton-end
 
+    // @skeleton-begin
+    // @skeleton: fn get_fees(&self, cents_per_gram: i32) -> ??? {
+    // @skeleton:     // Something goes here...
+    // @skeleton: }
     fn get_fees(&self, cents_per_gram: i32) -> i32 {
         self.weight_in_grams * cents_per_gram
     }
+    // @skeleton-end
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    #[should_panic]
-    fn fail_creating_weightless_package() {
-        let sender_country = String::from("Spain");
-        let recipient_country = String::from("Austria");
-
-        Package::new(sender_country, recipient_country, -2210);
-    }
-
-    #[test]
-    fn create_international_package() {
-        let sender_country = String::from("Spain");
-        let recipient_country = String::from("Russia");
-
-        let package = Package::new(sender_country, recipient_country, 1200);
-
-        assert!(package.is_international());
-    }
-
-    #[test]
-    fn create_local_package() {
-        let sender_country = String::from("Canada");
-        let recipient_country = sender_country.clone();
-
-        let package = Package::new(sender_country, recipient_country, 1200);
-
-        assert!(!package.is_international());
-    }
-
-    #[test]
-    fn calculate_transport_fees() {
-        let sender_country = String::from("Spain");
-        let recipient_country = String::from("Spain");
-
-        let cents_per_gram = 3;
-
-        let package = Package::new(sender_country, recipient_country, 1500);
-
-        assert_eq!(package.get_fees(cents_per_gram), 4500);
-        assert_eq!(package.get_fees(cents_per_gram * 2), 9000);
-    }
-}
+#[path = "tests.rs"]
+mod tests;
@@ -0,0 +1,14 @@
+// expect-error: E0382
+//
+// From "To Move or Not to Move": passing a String to a function moves it into
+// the function, which drops it when it returns.
+
+fn take_ownership(s: String) {
+    println!("I own {s:?}.");
+}
+
+fn main() {
+    let a = "Hi!".to_string();
+    take_ownership(a);
+    println!("{a}");
+}
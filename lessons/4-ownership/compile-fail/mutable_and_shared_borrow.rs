@@ -0,0 +1,12 @@
+// expect-error: E0502
+//
+// From "References and Borrowing": while a mutable borrow is alive, nothing
+// else may borrow the same data.
+
+fn main() {
+    let mut s = String::new();
+    let s2 = &mut s;
+    let s3 = &s;
+    s2.push_str("Hey!");
+    println!("{s3}");
+}
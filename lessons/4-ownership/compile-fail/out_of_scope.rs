@@ -0,0 +1,12 @@
+// expect-error: E0425
+//
+// From "The str Type": a variable is only valid inside the scope it's declared
+// in.
+
+fn main() {
+    {
+        let new_string = "New!";
+        println!("{new_string}");
+    }
+    println!("{new_string}");
+}
@@ -0,0 +1,10 @@
+// expect-error: E0382
+//
+// From "To Move or Not to Move": assigning a String to another variable moves
+// it, so the original variable can't be used anymore.
+
+fn main() {
+    let a = "Hi!".to_string();
+    let b = a;
+    println!("{a} {b}");
+}
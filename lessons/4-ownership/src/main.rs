@@ -5,6 +5,10 @@
 // the language. One of the critical concepts to grasp in Rust is that of
 // ownership.
 
+// Some of the lines below are commented out because they don't compile! Each of
+// them can also be found on its own in the "compile-fail" folder, where
+// `cargo run -p workshop -- compile-fail` checks that they fail the way we say.
+
 fn main() {
     // ******************** The "str" Type ********************
 
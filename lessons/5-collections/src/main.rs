@@ -31,7 +31,11 @@ fn main() {
     my_map.insert("New York", 8_804_190);
     my_map.insert("Philadelphia", 1_603_797);
 
-    for (key, value) in &my_map {
+    // A HashMap doesn't keep its entries in any particular order (it can even
+    // differ from one run to the next!), so we sort them before printing.
+    let mut cities: Vec<_> = my_map.iter().collect();
+    cities.sort();
+    for (key, value) in cities {
         println!("City: {:15} Population: {}", key, value);
     }
 
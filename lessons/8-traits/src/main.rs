@@ -1,6 +1,6 @@
 use std::ops;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 struct Complex64 {
     re: f32,
     im: f32,
@@ -102,11 +102,11 @@ impl ops::Neg for Complex64 {
 
 
 fn main() {
-    let mut x = Complex64::new(0.0, 1.0);
+    let x = Complex64::new(0.0, 1.0);
 
     println!("x = {}", x);
 
     let x_squared = x * x;
 
-    println!("x^2 = {}", x*x);
+    println!("x^2 = {}", x_squared);
 }
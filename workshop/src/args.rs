@@ -0,0 +1,82 @@
+// The command line of a command: flags that stand alone (like `--check`),
+// options that take a value (like `--out DIR`) and positional arguments, in any
+// order. Anything else starting with `-` is an unknown option.
+
+use crate::Result;
+
+pub struct Args {
+    positional: Vec<String>,
+    flags: Vec<String>,
+    values: Vec<(String, String)>,
+}
+
+impl Args {
+    // `options` pairs each option that takes a value with what that value is,
+    // for the error when it's missing: `("--out", "a directory")` gives
+    // "`--out` needs a directory".
+    pub fn parse(args: &[String], flags: &[&str], options: &[(&str, &str)]) -> Result<Args> {
+        let mut parsed = Args {
+            positional: Vec::new(),
+            flags: Vec::new(),
+            values: Vec::new(),
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if flags.contains(&arg.as_str()) {
+                parsed.flags.push(arg.clone());
+            } else if let Some((_, what)) = options.iter().find(|(option, _)| option == arg) {
+                let value = args.next().ok_or_else(|| format!("`{arg}` needs {what}"))?;
+                parsed.values.push((arg.clone(), value.clone()));
+            } else if arg.starts_with('-') {
+                return Err(format!("unknown option `{arg}`").into());
+            } else {
+                parsed.positional.push(arg.clone());
+            }
+        }
+        Ok(parsed)
+    }
+
+    pub fn flag(&self, flag: &str) -> bool {
+        self.flags.iter().any(|f| f == flag)
+    }
+
+    // The option's value, the last one if it was given more than once
+    pub fn value(&self, option: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .rev()
+            .find(|(o, _)| o == option)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn positional(&self) -> Vec<&str> {
+        self.positional.iter().map(String::as_str).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_args() {
+        let args = ["4", "--out", "a", "--toc", "8", "--out", "b"].map(String::from);
+        let parsed =
+            Args::parse(&args, &["--toc", "--check"], &[("--out", "a directory")]).unwrap();
+        assert_eq!(parsed.positional(), ["4", "8"]);
+        assert!(parsed.flag("--toc"));
+        assert!(!parsed.flag("--check"));
+        assert_eq!(parsed.value("--out"), Some("b"));
+        assert_eq!(parsed.value("--html"), None);
+
+        let error = |args: &[&str]| {
+            let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+            Args::parse(&args, &[], &[("--out", "a directory")])
+                .err()
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(error(&["--out"]), "`--out` needs a directory");
+        assert_eq!(error(&["4", "-x"]), "unknown option `-x`");
+    }
+}
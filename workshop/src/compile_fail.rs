@@ -0,0 +1,195 @@
+// Some lessons teach by showing code that doesn't compile, like using a String
+// after it has been moved. Each of those snippets lives in its own file under
+// `lessons/<lesson>/compile-fail/`, starting with the error it should produce:
+//
+//     // expect-error: E0382
+//
+// Here we compile each snippet with rustc and check that it fails, and fails
+// for the reason the lesson claims.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, ExitCode};
+
+use crate::args::Args;
+use crate::lesson::{self, Lesson};
+use crate::temp::TempDir;
+use crate::term::{self, Color};
+use crate::workspace::Workspace;
+use crate::Result;
+
+const EXPECT: &str = "// expect-error:";
+
+pub struct Case {
+    // Relative to the workspace root
+    pub path: PathBuf,
+    pub expected: String,
+}
+
+pub enum Verdict {
+    // Failed with the expected error
+    Pass,
+    // Compiled when it shouldn't have
+    Compiled,
+    // Failed, but with these other error codes
+    WrongError(Vec<String>),
+}
+
+// Every compile-fail snippet of the given lessons.
+pub fn cases(workspace: &Workspace, lessons: &[&Lesson]) -> Result<Vec<Case>> {
+    let mut cases = Vec::new();
+    for lesson in lessons {
+        let dir = lesson.dir.join("compile-fail");
+        if !dir.is_dir() {
+            continue;
+        }
+
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "rs") {
+                let source = fs::read_to_string(&path)?;
+                let path = path.strip_prefix(&workspace.root)?.to_path_buf();
+                let expected = expected_error(&source).ok_or_else(|| {
+                    format!("{} doesn't say which error it expects", path.display())
+                })?;
+                cases.push(Case { path, expected });
+            }
+        }
+    }
+    cases.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(cases)
+}
+
+fn expected_error(source: &str) -> Option<String> {
+    source
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(EXPECT))
+        .map(|code| code.trim().to_string())
+}
+
+pub fn check(workspace: &Workspace, case: &Case) -> Result<Verdict> {
+    let out = TempDir::new("compile-fail")?;
+    let output = Command::new(rustc())
+        .args([
+            "--edition",
+            "2021",
+            "--emit=metadata",
+            "--error-format=short",
+        ])
+        .arg("--out-dir")
+        .arg(out.path())
+        .arg(workspace.root.join(&case.path))
+        .output()
+        .map_err(|err| format!("failed to run rustc: {err}"))?;
+
+    if output.status.success() {
+        return Ok(Verdict::Compiled);
+    }
+
+    let codes = error_codes(&String::from_utf8_lossy(&output.stderr));
+    if codes.contains(&case.expected) {
+        Ok(Verdict::Pass)
+    } else {
+        Ok(Verdict::WrongError(codes))
+    }
+}
+
+// The distinct error codes in rustc's output, in order of appearance.
+pub fn error_codes(output: &str) -> Vec<String> {
+    let mut codes: Vec<String> = Vec::new();
+    for (i, _) in output.match_indices("error[E") {
+        let code: String = output[i + "error[".len()..]
+            .chars()
+            .take_while(|&c| c != ']')
+            .collect();
+        if !codes.contains(&code) {
+            codes.push(code);
+        }
+    }
+    codes
+}
+
+pub fn rustc() -> String {
+    env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string())
+}
+
+pub fn command(workspace: &Workspace, args: &[String]) -> Result<ExitCode> {
+    let args = Args::parse(args, &[], &[])?;
+    let lessons = lesson::select(workspace, &args.positional())?;
+    let cases = cases(workspace, &lessons.iter().collect::<Vec<_>>())?;
+    let mut rows = Vec::new();
+    let mut failed = 0;
+    for case in &cases {
+        let note = match check(workspace, case)? {
+            Verdict::Pass => None,
+            Verdict::Compiled => Some("compiled successfully".to_string()),
+            Verdict::WrongError(codes) => Some(format!("failed with {}", codes.join(", "))),
+        };
+        let result = match note {
+            None => ("PASS".to_string(), Some(Color::Green)),
+            Some(_) => {
+                failed += 1;
+                ("FAIL".to_string(), Some(Color::Red))
+            }
+        };
+        rows.push(vec![
+            (case.path.display().to_string(), None),
+            (case.expected.clone(), None),
+            result,
+            (note.unwrap_or_default(), Some(Color::Dim)),
+        ]);
+    }
+
+    term::print_table(&["Snippet", "Expected", "Result", ""], &rows);
+    println!(
+        "\n{}/{} snippets failed to compile as expected",
+        cases.len() - failed,
+        cases.len()
+    );
+    Ok(if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_expected_error() {
+        assert_eq!(
+            expected_error("// expect-error: E0382\n\nfn main() {}\n").as_deref(),
+            Some("E0382")
+        );
+        assert_eq!(expected_error("fn main() {}\n"), None);
+    }
+
+    #[test]
+    fn parse_error_codes() {
+        let output = "\
+a.rs:9:16: error[E0382]: borrow of moved value: `a`
+a.rs:12:5: error[E0502]: cannot borrow `s` as immutable
+a.rs:13:5: error[E0382]: borrow of moved value: `b`
+error: aborting due to 3 previous errors
+";
+        assert_eq!(error_codes(output), ["E0382", "E0502"]);
+    }
+
+    #[test]
+    fn lesson_snippets_fail_as_expected() {
+        let workspace = Workspace::ours();
+        let lessons = lesson::lessons(&workspace).unwrap();
+        let cases = cases(&workspace, &lessons.iter().collect::<Vec<_>>()).unwrap();
+        assert!(!cases.is_empty());
+        for case in &cases {
+            assert!(
+                matches!(check(&workspace, case).unwrap(), Verdict::Pass),
+                "{}",
+                case.path.display()
+            );
+        }
+    }
+}
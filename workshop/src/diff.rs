@@ -0,0 +1,179 @@
+// Line-by-line diffs between what a program was expected to print and what it
+// actually printed, rendered in the familiar unified format.
+
+use crate::term::{self, Color};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Line<'a> {
+    Same(&'a str),
+    // Only in the expected output
+    Removed(&'a str),
+    // Only in the actual output
+    Added(&'a str),
+}
+
+// Diffs two texts by their longest common subsequence of lines. Program output
+// in this workshop is short, so the quadratic table is no concern.
+pub fn lines<'a>(expected: &'a str, actual: &'a str) -> Vec<Line<'a>> {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+
+    // lcs[i][j] is the length of the LCS of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut diff = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            diff.push(Line::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            diff.push(Line::Removed(old[i]));
+            i += 1;
+        } else {
+            diff.push(Line::Added(new[j]));
+            j += 1;
+        }
+    }
+    diff
+}
+
+// The first line (counting from 1) of the actual output that doesn't match the
+// expected output, along with what was expected and what we got there.
+pub fn first_difference<'a>(
+    diff: &[Line<'a>],
+) -> Option<(usize, Option<&'a str>, Option<&'a str>)> {
+    let start = diff
+        .iter()
+        .position(|line| !matches!(line, Line::Same(_)))?;
+    let number = 1 + diff[..start].len();
+
+    let changed = diff[start..]
+        .iter()
+        .take_while(|line| !matches!(line, Line::Same(_)));
+    let expected = changed.clone().find_map(|line| match line {
+        Line::Removed(text) => Some(*text),
+        _ => None,
+    });
+    let actual = changed.clone().find_map(|line| match line {
+        Line::Added(text) => Some(*text),
+        _ => None,
+    });
+    Some((number, expected, actual))
+}
+
+// Renders the diff as unified hunks with `context` unchanged lines around each
+// change.
+pub fn unified(diff: &[Line], context: usize, names: (&str, &str), color: bool) -> String {
+    let paint = |text: String, c: Color| term::paint_with(&text, c, color);
+    let mut out = vec![
+        paint(format!("--- {}", names.0), Color::Red),
+        paint(format!("+++ {}", names.1), Color::Green),
+    ];
+
+    let changes: Vec<usize> = (0..diff.len())
+        .filter(|&i| !matches!(diff[i], Line::Same(_)))
+        .collect();
+
+    let mut i = 0;
+    while i < changes.len() {
+        // Grow the hunk for as long as the next change is close enough that the
+        // context around the two would touch.
+        let start = changes[i].saturating_sub(context);
+        let mut end = changes[i];
+        while i + 1 < changes.len() && changes[i + 1] <= end + 2 * context + 1 {
+            i += 1;
+            end = changes[i];
+        }
+        let end = (end + context + 1).min(diff.len());
+        i += 1;
+
+        // Line numbers on each side of the hunk
+        let old_start = 1 + diff[..start]
+            .iter()
+            .filter(|l| !matches!(l, Line::Added(_)))
+            .count();
+        let new_start = 1 + diff[..start]
+            .iter()
+            .filter(|l| !matches!(l, Line::Removed(_)))
+            .count();
+        let old_len = diff[start..end]
+            .iter()
+            .filter(|l| !matches!(l, Line::Added(_)))
+            .count();
+        let new_len = diff[start..end]
+            .iter()
+            .filter(|l| !matches!(l, Line::Removed(_)))
+            .count();
+        out.push(paint(
+            format!("@@ -{old_start},{old_len} +{new_start},{new_len} @@"),
+            Color::Cyan,
+        ));
+
+        for line in &diff[start..end] {
+            out.push(match line {
+                Line::Same(text) => format!(" {text}"),
+                Line::Removed(text) => paint(format!("-{text}"), Color::Red),
+                Line::Added(text) => paint(format!("+{text}"), Color::Green),
+            });
+        }
+    }
+
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_outputs() {
+        let diff = lines("0\n1\n1\n", "0\n1\n1\n");
+        assert!(diff.iter().all(|line| matches!(line, Line::Same(_))));
+        assert_eq!(first_difference(&diff), None);
+    }
+
+    #[test]
+    fn changed_line() {
+        let diff = lines("Debug print:\nQuit\n", "Debug print:\nExit\n");
+        assert_eq!(
+            diff,
+            [
+                Line::Same("Debug print:"),
+                Line::Removed("Quit"),
+                Line::Added("Exit")
+            ]
+        );
+        assert_eq!(
+            first_difference(&diff),
+            Some((2, Some("Quit"), Some("Exit")))
+        );
+    }
+
+    #[test]
+    fn missing_lines() {
+        let diff = lines("0\n1\n1\n2\n", "0\n1\n");
+        assert_eq!(first_difference(&diff), Some((3, Some("1"), None)));
+    }
+
+    #[test]
+    fn unified_hunks() {
+        let expected = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        let actual = "a\nB\nc\nd\ne\nf\ng\nH\n";
+        let text = unified(&lines(expected, actual), 1, ("expected", "actual"), false);
+        assert_eq!(
+            text,
+            "--- expected\n+++ actual\n@@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n@@ -7,2 +7,2 @@\n g\n-h\n+H"
+        );
+    }
+}
@@ -0,0 +1,476 @@
+// Grading builds an exercise with cargo and decides whether the student is done.
+// How that's decided depends on the exercise's mode in info.toml.
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::process::{Command, ExitCode, ExitStatus, Stdio};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::args::Args;
+use crate::diff;
+use crate::manifest::{Exercise, Manifest};
+use crate::progress::{self, Progress};
+use crate::report;
+use crate::term::{self, Color};
+use crate::workspace::Workspace;
+use crate::Result;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    // The crate's #[test]s have to pass, e.g. packages
+    Test,
+    // `main` has to run without panicking, on an `assert_eq!` for example.
+    // Nothing uses it right now: fibonacci did, until what it prints was
+    // graded too.
+    Assert,
+    // `main` has to print the same thing as the `<name>-solution` binary, e.g.
+    // messages
+    Output,
+}
+
+impl FromStr for Mode {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Mode, ()> {
+        match s {
+            "test" => Ok(Mode::Test),
+            "assert" => Ok(Mode::Assert),
+            "output" => Ok(Mode::Output),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mode::Test => write!(f, "test"),
+            Mode::Assert => write!(f, "assert"),
+            Mode::Output => write!(f, "output"),
+        }
+    }
+}
+
+pub struct Outcome {
+    pub passed: bool,
+    // Everything cargo and the program printed, stdout followed by stderr
+    pub output: String,
+    // Set when an output exercise ran fine but printed the wrong thing
+    pub mismatch: Option<Mismatch>,
+    pub elapsed: Duration,
+}
+
+pub struct Mismatch {
+    pub solution: String,
+    pub expected: String,
+    pub student: String,
+    pub actual: String,
+}
+
+impl Mismatch {
+    pub fn describe(&self, color: bool) -> String {
+        let diff = diff::lines(&self.expected, &self.actual);
+        let Some((line, expected, actual)) = diff::first_difference(&diff) else {
+            return String::new();
+        };
+
+        let show = |text: Option<&str>| match text {
+            Some(text) => format!("{text:?}"),
+            None => "nothing".to_string(),
+        };
+        format!(
+            "Line {line} of the output doesn't match {}\n    expected: {}\n         got: {}\n\n{}",
+            self.solution,
+            show(expected),
+            show(actual),
+            diff::unified(&diff, 3, (&self.solution, &self.student), color)
+        )
+    }
+}
+
+pub fn grade(workspace: &Workspace, exercise: &Exercise) -> Result<Outcome> {
+    let start = Instant::now();
+    let student = run_cargo(workspace, &cargo_args(exercise))?;
+
+    let mut outcome = Outcome {
+        passed: student.status.success(),
+        output: format!("{}{}", student.stdout, student.stderr),
+        mismatch: None,
+        elapsed: Duration::ZERO,
+    };
+
+    // Output exercises also have to print exactly what the solution prints.
+    if exercise.mode == Mode::Output && outcome.passed {
+        let solution_bin = format!("{}-solution", exercise.name);
+        let args = [
+            "run",
+            "--quiet",
+            "-p",
+            &exercise.name,
+            "--bin",
+            &solution_bin,
+        ];
+        let solution = run_cargo(workspace, &args.map(String::from))?;
+        if !solution.status.success() {
+            return Err(format!(
+                "the solution to `{}` failed to run\n{}{}",
+                exercise.name, solution.stdout, solution.stderr
+            )
+            .into());
+        }
+
+        if solution.stdout != student.stdout {
+            outcome.passed = false;
+            outcome.mismatch = Some(Mismatch {
+                solution: solution_bin,
+                expected: solution.stdout,
+                student: exercise.name.clone(),
+                actual: student.stdout,
+            });
+        }
+    }
+
+    outcome.elapsed = start.elapsed();
+    Ok(outcome)
+}
+
+pub struct CargoOutput {
+    pub status: ExitStatus,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+pub fn run_cargo(workspace: &Workspace, args: &[String]) -> Result<CargoOutput> {
+    let output = Command::new(cargo())
+        .args(args)
+        .current_dir(&workspace.root)
+        .env("RUST_BACKTRACE", "0")
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|err| format!("failed to run cargo: {err}"))?;
+
+    Ok(CargoOutput {
+        status: output.status,
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+    })
+}
+
+impl Outcome {
+    // The first thing that went wrong: either the first compiler error or the
+    // first failed assertion (or other panic), whichever shows up first. For
+    // output exercises, it can also be the first line that differs from the
+    // solution.
+    pub fn first_problem(&self, color: bool) -> Option<String> {
+        if let Some(mismatch) = &self.mismatch {
+            return Some(mismatch.describe(color));
+        }
+
+        let lines: Vec<&str> = self.output.lines().collect();
+        let start = lines.iter().position(|line| {
+            (line.starts_with("error") && !line.starts_with("error: could not compile"))
+                || (line.starts_with("thread '") && line.contains("panicked at"))
+        })?;
+
+        let problem: Vec<&str> = lines[start..]
+            .iter()
+            .take_while(|line| {
+                !line.trim().is_empty()
+                    && !line.starts_with("note:")
+                    && !line.starts_with("stack backtrace:")
+            })
+            .copied()
+            .collect();
+        Some(problem.join("\n"))
+    }
+}
+
+// The cargo invocation used to grade `exercise`, which we also show to the
+// student so they can reproduce a failure by hand.
+pub fn cargo_args(exercise: &Exercise) -> Vec<String> {
+    let args = match exercise.mode {
+        Mode::Test => vec!["test", "--quiet", "-p", &exercise.name],
+        Mode::Assert | Mode::Output => vec![
+            "run",
+            "--quiet",
+            "-p",
+            &exercise.name,
+            "--bin",
+            &exercise.name,
+        ],
+    };
+    args.into_iter().map(String::from).collect()
+}
+
+// The exercises named in `names`, or all of them if no names are given. Either
+// way they come back in the order of info.toml.
+pub fn select(manifest: Manifest, names: &[String]) -> Result<Vec<Exercise>> {
+    for name in names {
+        if !manifest.exercises.iter().any(|e| &e.name == name) {
+            return Err(format!("no exercise named `{name}`").into());
+        }
+    }
+    let mut exercises = manifest.exercises;
+    if !names.is_empty() {
+        exercises.retain(|e| names.contains(&e.name));
+    }
+    Ok(exercises)
+}
+
+// Respect the cargo that launched us (e.g. through `cargo run`), if any.
+pub fn cargo() -> String {
+    env::var("CARGO").unwrap_or_else(|_| "cargo".to_string())
+}
+
+pub fn command(workspace: &Workspace, args: &[String]) -> Result<ExitCode> {
+    let args = Args::parse(
+        args,
+        &[],
+        &[
+            ("--json", "a file to write"),
+            ("--junit", "a file to write"),
+        ],
+    )?;
+    let (json_path, junit_path) = (args.value("--json"), args.value("--junit"));
+    let names: Vec<String> = args.positional().into_iter().map(String::from).collect();
+    let exercises = select(Manifest::load(workspace)?, &names)?;
+
+    let progress = Progress::load(workspace)?;
+    let mut rows = Vec::new();
+    let mut results = Vec::new();
+    for exercise in &exercises {
+        println!(
+            "{} {}...",
+            term::paint("Grading", Color::Cyan),
+            exercise.name
+        );
+        let outcome = grade(workspace, exercise)?;
+        progress::record(workspace, &exercise.name, outcome.passed)?;
+
+        let result = if outcome.passed {
+            ("PASS".to_string(), Some(Color::Green))
+        } else {
+            ("FAIL".to_string(), Some(Color::Red))
+        };
+        rows.push(vec![
+            (exercise.name.clone(), None),
+            (exercise.mode.to_string(), None),
+            result,
+            (
+                format!(
+                    "{}/{}",
+                    progress.hints_used(&exercise.name),
+                    exercise.hints.len()
+                ),
+                None,
+            ),
+            (
+                format!("{:.1}s", outcome.elapsed.as_secs_f64()),
+                Some(Color::Dim),
+            ),
+        ]);
+        results.push((exercise, outcome));
+    }
+
+    let results: Vec<(&Exercise, &Outcome)> = results.iter().map(|(e, o)| (*e, o)).collect();
+    if let Some(path) = json_path {
+        fs::write(path, report::json(&results))
+            .map_err(|err| format!("failed to write {path}: {err}"))?;
+    }
+    if let Some(path) = junit_path {
+        fs::write(path, report::junit(&results))
+            .map_err(|err| format!("failed to write {path}: {err}"))?;
+    }
+    let failures: Vec<_> = results.into_iter().filter(|(_, o)| !o.passed).collect();
+
+    println!();
+    term::print_table(&["Exercise", "Mode", "Result", "Hints", "Time"], &rows);
+    println!();
+    println!(
+        "{}/{} exercises passed",
+        exercises.len() - failures.len(),
+        exercises.len()
+    );
+
+    if failures.is_empty() {
+        return Ok(ExitCode::SUCCESS);
+    }
+    // Running cargo by hand won't show a mismatch against the solution, so
+    // those get their diff printed here instead.
+    for (exercise, outcome) in &failures {
+        if let Some(mismatch) = &outcome.mismatch {
+            println!(
+                "\n{}: {}",
+                exercise.name,
+                mismatch.describe(term::use_color())
+            );
+        }
+    }
+    if failures
+        .iter()
+        .any(|(_, outcome)| outcome.mismatch.is_none())
+    {
+        println!("\nTo see what went wrong, run:");
+        for (exercise, _) in failures.iter().filter(|(_, o)| o.mismatch.is_none()) {
+            println!("    cargo {}", cargo_args(exercise).join(" "));
+        }
+    }
+
+    // Point at the lesson behind the first exercise that still needs work.
+    let next = failures[0].0;
+    println!(
+        "\nStuck on {}? It builds on {}, or run `workshop hint {}`.",
+        next.name,
+        next.lesson.display(),
+        next.name
+    );
+    Ok(ExitCode::FAILURE)
+}
+
+// What grading the packages exercise printed for a package.rs with the wrong
+// `is_international` and `get_fees`: cargo's stdout then its stderr (less the
+// compiler's warnings), as `cargo test --quiet` left them. Other modules' tests
+// read it too.
+#[cfg(test)]
+pub const FAILED_TESTS_OUTPUT: &str = "
+running 4 tests
+package::tests::calculate_transport_fees --- FAILED
+package::tests::create_international_package --- FAILED
+package::tests::create_local_package --- FAILED
+.
+failures:
+
+---- package::tests::calculate_transport_fees stdout ----
+
+thread 'package::tests::calculate_transport_fees' (31301) panicked at exercises/packages/src/tests.rs:41:5:
+assertion `left == right` failed
+  left: 0
+ right: 4500
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+---- package::tests::create_international_package stdout ----
+
+thread 'package::tests::create_international_package' (31302) panicked at exercises/packages/src/tests.rs:19:5:
+assertion failed: package.is_international()
+
+---- package::tests::create_local_package stdout ----
+
+thread 'package::tests::create_local_package' (31303) panicked at exercises/packages/src/tests.rs:29:5:
+assertion failed: !package.is_international()
+
+
+failures:
+    package::tests::calculate_transport_fees
+    package::tests::create_international_package
+    package::tests::create_local_package
+
+test result: FAILED. 1 passed; 3 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+error: test failed, to rerun pass `-p packages --lib`
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp::TempDir;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn outcome(output: &str) -> Outcome {
+        Outcome {
+            passed: false,
+            output: output.to_string(),
+            mismatch: None,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn first_compiler_error() {
+        let outcome = outcome(
+            "\
+error[E0425]: cannot find function `fibonacci` in this scope
+ --> exercises/fibonacci/src/main.rs:7:16
+  |
+7 |     assert_eq!(fibonacci(36), 14930352);
+  |                ^^^^^^^^^ not found in this scope
+
+For more information about this error, try `rustc --explain E0425`.
+error: could not compile `fibonacci` (bin \"fibonacci\") due to 1 previous error
+",
+        );
+
+        let problem = outcome.first_problem(false).unwrap();
+        assert!(problem.starts_with("error[E0425]: cannot find function `fibonacci`"));
+        assert_eq!(problem.lines().count(), 5);
+    }
+
+    #[test]
+    fn first_failed_assertion() {
+        let problem = outcome(FAILED_TESTS_OUTPUT).first_problem(false).unwrap();
+        assert_eq!(
+            problem,
+            "\
+thread 'package::tests::calculate_transport_fees' (31301) panicked at exercises/packages/src/tests.rs:41:5:
+assertion `left == right` failed
+  left: 0
+ right: 4500"
+        );
+    }
+
+    #[test]
+    fn assert_mode() {
+        // A made-up workspace with two exercises graded by their assertions
+        let root = TempDir::new("assert-mode").unwrap();
+        let root = root.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"passes\", \"fails\"]\n",
+        )
+        .unwrap();
+        for (name, sum) in [("passes", 2), ("fails", 3)] {
+            let dir = root.join(name);
+            fs::create_dir_all(dir.join("src")).unwrap();
+            fs::write(
+                dir.join("Cargo.toml"),
+                format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n"),
+            )
+            .unwrap();
+            // What it prints doesn't matter, only that main gets to the end.
+            fs::write(
+                dir.join("src/main.rs"),
+                format!("fn main() {{\n    println!(\"anything\");\n    assert_eq!(1 + 1, {sum});\n}}\n"),
+            )
+            .unwrap();
+        }
+        let workspace = Workspace::load(root).unwrap();
+        let grade_exercise = |name: &str| {
+            let exercise = Exercise {
+                name: name.to_string(),
+                path: PathBuf::from(name),
+                dir: root.join(name),
+                mode: Mode::Assert,
+                lesson: PathBuf::from("lessons/3-nuts-and-bolts"),
+                hints: Vec::new(),
+                skeleton: PathBuf::from("src/main.rs"),
+            };
+            grade(&workspace, &exercise).unwrap()
+        };
+
+        let outcome = grade_exercise("passes");
+        assert!(outcome.passed, "{}", outcome.output);
+        assert!(outcome.mismatch.is_none());
+        let outcome = grade_exercise("fails");
+        assert!(!outcome.passed);
+        let problem = outcome.first_problem(false).unwrap();
+        assert!(problem.starts_with("thread 'main'"), "{problem}");
+        assert!(problem.contains("right: 3"), "{problem}");
+    }
+
+    #[test]
+    fn no_problem() {
+        assert_eq!(outcome("0\n1\n1\n2\n").first_problem(false), None);
+    }
+}
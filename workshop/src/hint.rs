@@ -0,0 +1,63 @@
+// Hints come in levels, and are handed out one at a time: first a nudge, then a
+// pointer to the concept (and where the lessons cover it), and finally
+// something close to the solution. How many were needed is recorded, and shows
+// up next to the grading results.
+
+use std::process::ExitCode;
+
+use crate::manifest::Manifest;
+use crate::progress::Progress;
+use crate::term::{self, Color};
+use crate::workspace::Workspace;
+use crate::Result;
+
+const LEVELS: [&str; 3] = ["nudge", "concept", "near-solution"];
+
+pub fn level_name(level: usize) -> &'static str {
+    LEVELS.get(level).copied().unwrap_or("hint")
+}
+
+pub fn command(workspace: &Workspace, args: &[String]) -> Result<ExitCode> {
+    let [name] = args else {
+        return Err("usage: workshop hint EXERCISE".into());
+    };
+
+    let manifest = Manifest::load(workspace)?;
+    let exercise = manifest
+        .exercises
+        .iter()
+        .find(|e| &e.name == name)
+        .ok_or_else(|| format!("no exercise named `{name}`"))?;
+
+    let mut progress = Progress::load(workspace)?;
+    let used = progress.hints_used(name);
+    let total = exercise.hints.len();
+
+    // Once every hint has been revealed, keep showing the last one.
+    let level = used.min(total - 1);
+    let title = format!(
+        "Hint {}/{total} for {name} ({}):",
+        level + 1,
+        level_name(level)
+    );
+    println!(
+        "{}\n{}",
+        term::paint(&title, Color::Cyan),
+        exercise.hints[level]
+    );
+
+    if used < total {
+        progress.hints.insert(name.clone(), used + 1);
+        progress.save(workspace)?;
+    }
+    if level + 1 < total {
+        println!(
+            "{}",
+            term::paint(
+                "\nStill stuck? Run this again for the next hint.",
+                Color::Dim
+            )
+        );
+    }
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,426 @@
+// Tools for whoever runs the workshop. `instructor report DIR` summarizes a
+// whole cohort from the files each student sends in: one directory per student
+// under DIR, holding their `progress` file and, if they ran
+// `workshop grade --json report.json`, their `report.json`. Sending in the
+// whole `.workshop` directory works too.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use crate::args::Args;
+use crate::json::{self, Value};
+use crate::lesson::{self, Lesson};
+use crate::manifest::Manifest;
+use crate::progress::Progress;
+use crate::report::escape_xml;
+use crate::term::{self, Color};
+use crate::workspace::Workspace;
+use crate::Result;
+
+// How many of the most common failing tests and error codes to list
+const TOP: usize = 10;
+
+pub struct Student {
+    pub name: String,
+    pub progress: Progress,
+    // Per exercise, the tests that failed in the student's last report
+    pub failing_tests: BTreeMap<String, Vec<String>>,
+    // Every compiler error code in that report
+    pub error_codes: Vec<String>,
+}
+
+// A titled table, which ends up either in the terminal or in the HTML page.
+pub struct Section {
+    pub title: String,
+    pub header: Vec<String>,
+    pub rows: Vec<Vec<(String, Option<Color>)>>,
+}
+
+pub fn load_students(dir: &Path) -> Result<Vec<Student>> {
+    let mut entries: Vec<_> = fs::read_dir(dir)
+        .map_err(|err| format!("failed to read {}: {err}", dir.display()))?
+        .collect::<std::result::Result<_, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    let mut students = Vec::new();
+    for entry in entries {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let Some(progress_path) = find(&path, "progress") else {
+            eprintln!(
+                "{} skipping {name}, there's no progress file in {}",
+                term::paint("warning:", Color::Red),
+                path.display()
+            );
+            continue;
+        };
+
+        let mut student = Student {
+            name,
+            progress: Progress::read(&progress_path)?,
+            failing_tests: BTreeMap::new(),
+            error_codes: Vec::new(),
+        };
+        if let Some(report_path) = find(&path, "report.json") {
+            let text = fs::read_to_string(&report_path)?;
+            let report = json::parse(&text)
+                .map_err(|err| format!("failed to parse {}: {err}", report_path.display()))?;
+            read_report(&mut student, &report);
+        }
+        students.push(student);
+    }
+    Ok(students)
+}
+
+// Either straight in the student's directory, or in a `.workshop` directory
+// inside it.
+fn find(dir: &Path, file: &str) -> Option<PathBuf> {
+    [dir.join(file), dir.join(".workshop").join(file)]
+        .into_iter()
+        .find(|path| path.is_file())
+}
+
+fn read_report(student: &mut Student, report: &Value) {
+    let strings = |value: Option<&Value>| -> Vec<String> {
+        value
+            .and_then(Value::as_array)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect()
+    };
+
+    let exercises = report.get("exercises").and_then(Value::as_array);
+    for exercise in exercises.unwrap_or_default() {
+        let Some(name) = exercise.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        let tests = strings(exercise.get("failing_tests"));
+        if !tests.is_empty() {
+            student.failing_tests.insert(name.to_string(), tests);
+        }
+        for code in strings(exercise.get("error_codes")) {
+            if !student.error_codes.contains(&code) {
+                student.error_codes.push(code);
+            }
+        }
+    }
+}
+
+pub fn summarize(manifest: &Manifest, lessons: &[Lesson], students: &[Student]) -> Vec<Section> {
+    let exercises = &manifest.exercises;
+
+    // Who has passed what, and how many hints it took
+    let mut header = vec!["Student".to_string()];
+    header.extend(exercises.iter().map(|e| e.name.clone()));
+    header.push("Lessons read".to_string());
+    let mut rows = Vec::new();
+    for student in students {
+        let mut row = vec![(student.name.clone(), None)];
+        for exercise in exercises {
+            let hints = student.progress.hints_used(&exercise.name);
+            let hints = if hints > 0 {
+                format!(" ({hints} hints)")
+            } else {
+                String::new()
+            };
+            row.push(match student.progress.passed(&exercise.name) {
+                Some(true) => (format!("PASS{hints}"), Some(Color::Green)),
+                Some(false) => (format!("FAIL{hints}"), Some(Color::Red)),
+                None => (format!("-{hints}"), Some(Color::Dim)),
+            });
+        }
+        // Only lessons that still exist count, not ones since renamed or removed
+        let read = lessons
+            .iter()
+            .filter(|lesson| student.progress.has_read(&lesson.name))
+            .count();
+        row.push((format!("{read}/{}", lessons.len()), None));
+        rows.push(row);
+    }
+    let completion = Section {
+        title: format!(
+            "Completion ({} {})",
+            students.len(),
+            if students.len() == 1 {
+                "student"
+            } else {
+                "students"
+            }
+        ),
+        header,
+        rows,
+    };
+
+    let mut rows = Vec::new();
+    for exercise in exercises {
+        let passed = students
+            .iter()
+            .filter(|s| s.progress.passed(&exercise.name) == Some(true))
+            .count();
+        let attempted = students
+            .iter()
+            .filter(|s| s.progress.passed(&exercise.name).is_some())
+            .count();
+        let hints: Vec<usize> = students
+            .iter()
+            .map(|s| s.progress.hints_used(&exercise.name))
+            .collect();
+        let average = hints.iter().sum::<usize>() as f64 / students.len().max(1) as f64;
+        let all = hints.iter().filter(|&&h| h >= exercise.hints.len()).count();
+        rows.push(vec![
+            (exercise.name.clone(), None),
+            (format!("{passed}/{}", students.len()), None),
+            (format!("{attempted}/{}", students.len()), None),
+            (format!("{average:.1}"), None),
+            (all.to_string(), None),
+        ]);
+    }
+    let hints = Section {
+        title: "Hint usage".to_string(),
+        header: [
+            "Exercise",
+            "Passed",
+            "Attempted",
+            "Hints (average)",
+            "Used every hint",
+        ]
+        .map(String::from)
+        .to_vec(),
+        rows,
+    };
+
+    let mut tests: BTreeMap<(String, String), usize> = BTreeMap::new();
+    let mut codes: BTreeMap<String, usize> = BTreeMap::new();
+    for student in students {
+        for (exercise, failed) in &student.failing_tests {
+            for test in failed {
+                *tests.entry((exercise.clone(), test.clone())).or_default() += 1;
+            }
+        }
+        for code in &student.error_codes {
+            *codes.entry(code.clone()).or_default() += 1;
+        }
+    }
+    let tests = Section {
+        title: "Most common failing tests".to_string(),
+        header: ["Students", "Exercise", "Test"].map(String::from).to_vec(),
+        rows: most_common(tests)
+            .into_iter()
+            .map(|((exercise, test), count)| {
+                vec![(count.to_string(), None), (exercise, None), (test, None)]
+            })
+            .collect(),
+    };
+    let codes = Section {
+        title: "Most common compiler errors".to_string(),
+        header: ["Students", "Error"].map(String::from).to_vec(),
+        rows: most_common(codes)
+            .into_iter()
+            .map(|(code, count)| vec![(count.to_string(), None), (code, None)])
+            .collect(),
+    };
+
+    vec![completion, hints, tests, codes]
+}
+
+// The most frequent keys first, ties broken by the key itself.
+fn most_common<K: Ord>(counts: BTreeMap<K, usize>) -> Vec<(K, usize)> {
+    let mut counts: Vec<(K, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts.truncate(TOP);
+    counts
+}
+
+pub fn html(sections: &[Section]) -> String {
+    let mut out = String::from(
+        "\
+<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>Workshop report</title>
+<style>
+  body { font-family: system-ui, sans-serif; margin: 2em; color: #222; }
+  table { border-collapse: collapse; margin-bottom: 2em; }
+  th, td { border: 1px solid #ccc; padding: 0.3em 0.8em; text-align: left; }
+  th { background: #f3f3f3; }
+  .pass { background: #dff5e1; }
+  .fail { background: #fbe0e0; }
+  .none { color: #999; }
+</style>
+</head>
+<body>
+<h1>Workshop report</h1>
+",
+    );
+    for section in sections {
+        out.push_str(&format!("<h2>{}</h2>\n", escape_xml(&section.title)));
+        if section.rows.is_empty() {
+            out.push_str("<p>Nothing to report.</p>\n");
+            continue;
+        }
+        out.push_str("<table>\n<tr>");
+        for cell in &section.header {
+            out.push_str(&format!("<th>{}</th>", escape_xml(cell)));
+        }
+        out.push_str("</tr>\n");
+        for row in &section.rows {
+            out.push_str("<tr>");
+            for (cell, color) in row {
+                let class = match color {
+                    Some(Color::Green) => " class=\"pass\"",
+                    Some(Color::Red) => " class=\"fail\"",
+                    Some(Color::Dim) => " class=\"none\"",
+                    _ => "",
+                };
+                out.push_str(&format!("<td{class}>{}</td>", escape_xml(cell)));
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+pub fn command(workspace: &Workspace, args: &[String]) -> Result<ExitCode> {
+    let usage = "usage: workshop instructor report DIR [--html FILE]";
+    let Some(("report", args)) = args.split_first().map(|(c, rest)| (c.as_str(), rest)) else {
+        return Err(usage.into());
+    };
+
+    let args = Args::parse(args, &[], &[("--html", "a file to write")])?;
+    let html_path = args.value("--html");
+    let [dir] = args.positional()[..] else {
+        return Err(usage.into());
+    };
+
+    let manifest = Manifest::load(workspace)?;
+    let lessons = lesson::lessons(workspace)?;
+    let students = load_students(Path::new(dir))?;
+    if students.is_empty() {
+        return Err(format!("no student progress files found in {dir}").into());
+    }
+
+    let sections = summarize(&manifest, &lessons, &students);
+    for (i, section) in sections.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{}", term::paint(&section.title, Color::Bold));
+        if section.rows.is_empty() {
+            println!("{}", term::paint("Nothing to report.", Color::Dim));
+            continue;
+        }
+        let header: Vec<&str> = section.header.iter().map(String::as_str).collect();
+        term::print_table(&header, &section.rows);
+    }
+
+    if let Some(path) = html_path {
+        fs::write(path, html(&sections)).map_err(|err| format!("failed to write {path}: {err}"))?;
+        println!("\nWrote {path}");
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grade::{Outcome, FAILED_TESTS_OUTPUT};
+    use crate::report;
+    use crate::temp::TempDir;
+    use std::time::Duration;
+
+    #[test]
+    fn summarize_a_cohort() {
+        let workspace = Workspace::ours();
+        let manifest = Manifest::load(&workspace).unwrap();
+
+        let dir = TempDir::new("instructor").unwrap();
+        // The report each student sends in, as `workshop grade --json` writes it
+        let exercise = |name| manifest.exercises.iter().find(|e| e.name == name).unwrap();
+        let failed = |output: &str| Outcome {
+            passed: false,
+            output: output.to_string(),
+            mismatch: None,
+            elapsed: Duration::ZERO,
+        };
+        let packages = failed(FAILED_TESTS_OUTPUT);
+        let messages = failed(
+            "\
+error[E0599]: no variant named `Move` found for enum `Message`
+  --> exercises/messages/src/main.rs:25:18
+   |
+ 4 | enum Message {
+   | ------------ variant `Move` not found here
+...
+25 |         Message::Move { x: 10, y: 30 },
+   |                  ^^^^ variant not found in `Message`
+
+error: could not compile `messages` (bin \"messages\") due to 1 previous error
+",
+        );
+        let report_json = report::json(&[
+            (exercise("packages"), &packages),
+            (exercise("messages"), &messages),
+        ]);
+        for (name, progress, report) in [
+            (
+                "alice",
+                "[exercises]\nfibonacci = \"passed\"\npackages = \"failed\"\n\n[hints]\npackages = 3\n\n[lessons]\n1-hello-world = true\n0-renamed-lesson = true\n",
+                Some(report_json.as_str()),
+            ),
+            ("bob", "[exercises]\npackages = \"failed\"\n", Some(report_json.as_str())),
+            ("carol", "[exercises]\nfibonacci = \"passed\"\n", None),
+        ] {
+            let student = dir.path().join(name).join(".workshop");
+            fs::create_dir_all(&student).unwrap();
+            fs::write(student.join("progress"), progress).unwrap();
+            if let Some(report) = report {
+                fs::write(student.join("report.json"), report).unwrap();
+            }
+        }
+        // Not a student
+        fs::write(dir.path().join("README"), "").unwrap();
+
+        let students = load_students(dir.path()).unwrap();
+        let names: Vec<&str> = students.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["alice", "bob", "carol"]);
+
+        let lessons = lesson::lessons(&workspace).unwrap();
+        let sections = summarize(&manifest, &lessons, &students);
+        let cells = |section: &Section, row: usize| -> Vec<String> {
+            section.rows[row]
+                .iter()
+                .map(|(cell, _)| cell.clone())
+                .collect()
+        };
+        assert_eq!(
+            cells(&sections[0], 0),
+            ["alice", "PASS", "FAIL (3 hints)", "-", "1/9"]
+        );
+        assert_eq!(
+            cells(&sections[1], 0),
+            ["fibonacci", "2/3", "2/3", "0.0", "0"]
+        );
+        assert_eq!(
+            cells(&sections[1], 1),
+            ["packages", "0/3", "2/3", "1.0", "1"]
+        );
+        assert_eq!(
+            cells(&sections[2], 0),
+            ["2", "packages", "package::tests::calculate_transport_fees"]
+        );
+        assert_eq!(sections[2].rows.len(), 3);
+        assert_eq!(cells(&sections[3], 0), ["2", "E0599"]);
+
+        let page = html(&sections);
+        assert!(page.contains("<td class=\"fail\">FAIL (3 hints)</td>"));
+    }
+}
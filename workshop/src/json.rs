@@ -0,0 +1,436 @@
+// Just enough JSON to write the grading reports, and to read them back when
+// summarizing a cohort. Objects keep their fields in the order they were given,
+// so the reports read top to bottom the way you'd expect. (Why not serde_json?
+// See workshop/Cargo.toml.)
+
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    // The value of an object's field, if this is an object and has one.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Value {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Value {
+        Value::String(s)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Value {
+        Value::Bool(b)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(n: f64) -> Value {
+        Value::Number(n)
+    }
+}
+
+impl From<usize> for Value {
+    fn from(n: usize) -> Value {
+        Value::Number(n as f64)
+    }
+}
+
+// Pretty-printed with two spaces of indentation, and a trailing newline.
+pub fn write(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value, 0);
+    out.push('\n');
+    out
+}
+
+fn write_value(out: &mut String, value: &Value, indent: usize) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(&b.to_string()),
+        // Rust prints whole floats without a fraction, which is valid JSON.
+        Value::Number(n) if n.is_finite() => out.push_str(&n.to_string()),
+        Value::Number(_) => out.push_str("null"),
+        Value::String(s) => write_string(out, s),
+        Value::Array(values) if values.is_empty() => out.push_str("[]"),
+        Value::Array(values) => {
+            out.push('[');
+            for (i, value) in values.iter().enumerate() {
+                out.push_str(if i == 0 { "\n" } else { ",\n" });
+                push_indent(out, indent + 1);
+                write_value(out, value, indent + 1);
+            }
+            out.push('\n');
+            push_indent(out, indent);
+            out.push(']');
+        }
+        Value::Object(fields) if fields.is_empty() => out.push_str("{}"),
+        Value::Object(fields) => {
+            out.push('{');
+            for (i, (key, value)) in fields.iter().enumerate() {
+                out.push_str(if i == 0 { "\n" } else { ",\n" });
+                push_indent(out, indent + 1);
+                write_string(out, key);
+                out.push_str(": ");
+                write_value(out, value, indent + 1);
+            }
+            out.push('\n');
+            push_indent(out, indent);
+            out.push('}');
+        }
+    }
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn push_indent(out: &mut String, indent: usize) {
+    for _ in 0..indent {
+        out.push_str("  ");
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseError {
+    line: usize,
+    message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+pub fn parse(input: &str) -> Result<Value, ParseError> {
+    let mut parser = Parser {
+        chars: input.chars().collect(),
+        pos: 0,
+        line: 1,
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.peek().is_some() {
+        return Err(parser.error("unexpected text after the value"));
+    }
+    Ok(value)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+}
+
+impl Parser {
+    fn value(&mut self) -> Result<Value, ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => {
+                self.bump();
+                self.object()
+            }
+            Some('[') => {
+                self.bump();
+                self.array()
+            }
+            Some('"') => {
+                self.bump();
+                self.string().map(Value::String)
+            }
+            Some('t') if self.eat_str("true") => Ok(Value::Bool(true)),
+            Some('f') if self.eat_str("false") => Ok(Value::Bool(false)),
+            Some('n') if self.eat_str("null") => Ok(Value::Null),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, ParseError> {
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.eat('}') {
+            return Ok(Value::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            self.expect('"')?;
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            let value = self.value()?;
+            fields.push((key, value));
+
+            self.skip_whitespace();
+            if self.eat('}') {
+                return Ok(Value::Object(fields));
+            }
+            self.expect(',')?;
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, ParseError> {
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.eat(']') {
+            return Ok(Value::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            if self.eat(']') {
+                return Ok(Value::Array(values));
+            }
+            self.expect(',')?;
+        }
+    }
+
+    // Called just after the opening quote.
+    fn string(&mut self) -> Result<String, ParseError> {
+        let mut out = String::new();
+        loop {
+            match self.bump() {
+                None | Some('\n') => return Err(self.error("unterminated string")),
+                Some('"') => return Ok(out),
+                Some('\\') => {
+                    let c = match self.bump() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => self.unicode_escape()?,
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    out.push(c);
+                }
+                Some(c) => out.push(c),
+            }
+        }
+    }
+
+    // Characters outside the basic plane come as a pair of \u escapes.
+    fn unicode_escape(&mut self) -> Result<char, ParseError> {
+        let high = self.hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            if !self.eat_str("\\u") {
+                return Err(self.error("unpaired surrogate"));
+            }
+            let low = self.hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("unpaired surrogate"));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, ParseError> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = self
+                .bump()
+                .and_then(|c| c.to_digit(16))
+                .ok_or_else(|| self.error("invalid unicode escape"))?;
+            code = code * 16 + digit;
+        }
+        Ok(code)
+    }
+
+    fn number(&mut self) -> Result<Value, ParseError> {
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit() || "+-.eE".contains(c)) {
+            self.bump();
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        text.parse()
+            .map(Value::Number)
+            .map_err(|_| self.error(&format!("invalid number `{text}`")))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\r' | '\n')) {
+            self.bump();
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_str(&mut self, s: &str) -> bool {
+        let matches = s
+            .chars()
+            .enumerate()
+            .all(|(i, c)| self.chars.get(self.pos + i) == Some(&c));
+        if matches {
+            for _ in s.chars() {
+                self.bump();
+            }
+        }
+        matches
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ParseError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{c}`")))
+        }
+    }
+
+    fn error(&self, message: &str) -> ParseError {
+        ParseError {
+            line: self.line,
+            message: message.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_nested_values() {
+        let value = Value::Object(vec![
+            ("name".to_string(), "fibonacci".into()),
+            ("passed".to_string(), false.into()),
+            ("elapsed".to_string(), 1.5.into()),
+            ("tests".to_string(), Value::Array(vec![2usize.into()])),
+            ("codes".to_string(), Value::Array(vec![])),
+        ]);
+        assert_eq!(
+            write(&value),
+            "\
+{
+  \"name\": \"fibonacci\",
+  \"passed\": false,
+  \"elapsed\": 1.5,
+  \"tests\": [
+    2
+  ],
+  \"codes\": []
+}
+"
+        );
+    }
+
+    #[test]
+    fn escape_strings() {
+        let value = Value::from("say \"hi\"\n\tC:\\ \u{1b}[31m");
+        assert_eq!(
+            write(&value),
+            "\"say \\\"hi\\\"\\n\\tC:\\\\ \\u001b[31m\"\n"
+        );
+    }
+
+    #[test]
+    fn parse_round_trips() {
+        let value = Value::Object(vec![
+            ("name".to_string(), "packages".into()),
+            ("passed".to_string(), true.into()),
+            ("elapsed".to_string(), 0.25.into()),
+            ("problem".to_string(), Value::Null),
+            (
+                "failing_tests".to_string(),
+                Value::Array(vec!["tests::a\n\"b\"".into(), Value::Array(vec![])]),
+            ),
+        ]);
+        assert_eq!(parse(&write(&value)).unwrap(), value);
+        assert_eq!(
+            value
+                .get("failing_tests")
+                .and_then(Value::as_array)
+                .unwrap()[0]
+                .as_str(),
+            Some("tests::a\n\"b\"")
+        );
+    }
+
+    #[test]
+    fn parse_escapes_and_errors() {
+        assert_eq!(
+            parse(r#""\u00e9\ud83e\udd80\/""#).unwrap(),
+            Value::from("é🦀/")
+        );
+        assert_eq!(parse(" -1.5e2 ").unwrap(), Value::Number(-150.0));
+
+        let err = parse("{\n  \"a\": 1,\n  \"b\" 2\n}").unwrap_err();
+        assert_eq!(err.to_string(), "line 3: expected `:`");
+        assert!(parse("[1, 2").is_err());
+        assert!(parse("{} {}").is_err());
+    }
+}
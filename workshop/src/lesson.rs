@@ -0,0 +1,376 @@
+// The lessons under lessons/, which are meant to be read in the order of the
+// number they start with. Reading one is something only you know you've done,
+// so `workshop mark-read` is how you tell the workshop about it.
+//
+// Most lessons are crates in the workspace, but the very first one is a lone
+// main.rs, built with plain rustc before cargo has even been introduced (which
+// is why the workspace excludes it). `workshop lessons` builds and runs both
+// kinds, and checks what they print against the golden expected-output.txt next
+// to them, so a change to a lesson can't silently change what students see.
+// `workshop lessons --update` rewrites the golden files from what the lessons
+// print now. Lessons that read files or stdin get them from their `[[lessons]]`
+// entry in info.toml.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode, Output, Stdio};
+use std::thread;
+
+use crate::args::Args;
+use crate::compile_fail::rustc;
+use crate::grade::cargo;
+use crate::manifest::{LessonInput, Manifest};
+use crate::progress::Progress;
+use crate::snapshot;
+use crate::temp::TempDir;
+use crate::term::{self, Color};
+use crate::workspace::{Crate, Workspace};
+use crate::Result;
+
+// What a lesson should print, next to its sources
+pub const EXPECTED_OUTPUT: &str = "expected-output.txt";
+
+#[derive(Clone)]
+pub struct Lesson {
+    // The directory name, e.g. "4-ownership"
+    pub name: String,
+    pub number: u32,
+    // Relative to the workspace root, e.g. "lessons/4-ownership"
+    pub path: PathBuf,
+    pub dir: PathBuf,
+    pub kind: Kind,
+}
+
+#[derive(Clone)]
+pub enum Kind {
+    // A single main.rs, compiled with `rustc main.rs`
+    Rustc,
+    // A workspace member with this package name
+    Cargo(String),
+}
+
+// How building and running a lesson went
+pub struct Run {
+    pub built: bool,
+    pub success: bool,
+    pub stdout: String,
+    // The compiler's output if the build failed, otherwise the program's
+    pub stderr: String,
+}
+
+impl Lesson {
+    // The file the lesson is written in
+    pub fn source(&self) -> PathBuf {
+        match self.kind {
+            Kind::Rustc => self.dir.join("main.rs"),
+            Kind::Cargo(_) => self.dir.join("src/main.rs"),
+        }
+    }
+}
+
+pub fn lessons(workspace: &Workspace) -> Result<Vec<Lesson>> {
+    let mut lessons = Vec::new();
+    for entry in fs::read_dir(workspace.root.join("lessons"))? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let Some(number) = name.split('-').next().and_then(|n| n.parse().ok()) else {
+            continue;
+        };
+        let path = PathBuf::from("lessons").join(&name);
+        let dir = workspace.root.join(&path);
+        let kind = if dir.join("Cargo.toml").is_file() {
+            Kind::Cargo(Crate::load(&dir)?.name)
+        } else if dir.join("main.rs").is_file() {
+            Kind::Rustc
+        } else {
+            return Err(
+                format!("{} has neither a Cargo.toml nor a main.rs", path.display()).into(),
+            );
+        };
+        lessons.push(Lesson {
+            name,
+            number,
+            path,
+            dir,
+            kind,
+        });
+    }
+    lessons.sort_by_key(|lesson| lesson.number);
+    Ok(lessons)
+}
+
+// Finds a lesson by its directory name ("4-ownership"), its number ("4"), its
+// title ("ownership") or its path ("lessons/4-ownership").
+pub fn find<'a>(lessons: &'a [Lesson], query: &str) -> Result<&'a Lesson> {
+    let query = query.trim_end_matches('/');
+    lessons
+        .iter()
+        .find(|lesson| {
+            lesson.name == query
+                || lesson.number.to_string() == query
+                || lesson
+                    .name
+                    .split_once('-')
+                    .is_some_and(|(_, title)| title == query)
+                || lesson.path == Path::new(query)
+        })
+        .ok_or_else(|| format!("no lesson called `{query}`").into())
+}
+
+// The lessons `queries` name, in that order, or every lesson if there are none.
+pub fn select(workspace: &Workspace, queries: &[&str]) -> Result<Vec<Lesson>> {
+    let all = lessons(workspace)?;
+    if queries.is_empty() {
+        return Ok(all);
+    }
+    queries
+        .iter()
+        .map(|query| find(&all, query).cloned())
+        .collect()
+}
+
+// Builds the lesson and runs it in a scratch directory holding only its
+// fixtures, with its scripted stdin (or nothing) as input, so that what it
+// prints doesn't depend on where it's run from.
+pub fn run(workspace: &Workspace, lesson: &Lesson, input: Option<&LessonInput>) -> Result<Run> {
+    let out = TempDir::new("lesson")?;
+    let cwd = TempDir::new("lesson-cwd")?;
+    copy_fixtures(lesson, input, cwd.path())?;
+    let stdin = input.and_then(|i| i.stdin.as_deref());
+    let (mut build, mut program) = match &lesson.kind {
+        Kind::Rustc => {
+            let binary = out.path().join(&lesson.name);
+            let mut build = Command::new(rustc());
+            build
+                .args(["--edition", "2021", "-o"])
+                .arg(&binary)
+                .arg(lesson.dir.join("main.rs"));
+            (build, Command::new(binary))
+        }
+        Kind::Cargo(package) => {
+            let mut build = Command::new(cargo());
+            build.args(["build", "--quiet", "-p", package]);
+            let mut program = Command::new(cargo());
+            program
+                .args(["run", "--quiet", "--manifest-path"])
+                .arg(workspace.root.join("Cargo.toml"))
+                .args(["-p", package]);
+            (build, program)
+        }
+    };
+
+    let build = output(build.current_dir(&workspace.root), None)?;
+    if !build.status.success() {
+        return Ok(Run {
+            built: false,
+            success: false,
+            stdout: String::new(),
+            stderr: String::from_utf8_lossy(&build.stderr).into_owned(),
+        });
+    }
+
+    let output = output(
+        program.current_dir(cwd.path()).env("RUST_BACKTRACE", "0"),
+        stdin,
+    )?;
+    Ok(Run {
+        built: true,
+        success: output.status.success(),
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+    })
+}
+
+// Copies the lesson's fixture files into `dir`, which is where it will run.
+pub fn copy_fixtures(lesson: &Lesson, input: Option<&LessonInput>, dir: &Path) -> Result<()> {
+    for fixture in input.map(|i| &i.fixtures[..]).unwrap_or_default() {
+        let name = fixture
+            .file_name()
+            .ok_or_else(|| format!("fixture {} has no file name", fixture.display()))?;
+        fs::copy(lesson.dir.join(fixture), dir.join(name))
+            .map_err(|err| format!("failed to copy fixture {}: {err}", fixture.display()))?;
+    }
+    Ok(())
+}
+
+fn output(command: &mut Command, stdin: Option<&str>) -> Result<Output> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let Some(stdin) = stdin else {
+        return command
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|err| format!("failed to run {program}: {err}").into());
+    };
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("failed to run {program}: {err}"))?;
+    // Feed stdin from another thread, so a program that prints a lot before
+    // reading can't leave both sides waiting on full pipes. It's fine if the
+    // program exits without reading all of it.
+    let mut pipe = child.stdin.take().unwrap();
+    let stdin = stdin.to_string();
+    let writer = thread::spawn(move || {
+        let _ = pipe.write_all(stdin.as_bytes());
+    });
+    let output = child.wait_with_output()?;
+    let _ = writer.join();
+    Ok(output)
+}
+
+// Why a run doesn't count, if it doesn't: it has to build, exit successfully
+// and print what's expected.
+pub fn problem(lesson: &Lesson, run: &Run) -> Result<Option<String>> {
+    if !run.built {
+        return Ok(Some(format!(
+            "failed to compile\n{}",
+            run.stderr.trim_end()
+        )));
+    }
+    if !run.success {
+        return Ok(Some(format!("failed to run\n{}", run.stderr.trim_end())));
+    }
+
+    let path = lesson.dir.join(EXPECTED_OUTPUT);
+    if !path.is_file() {
+        return Ok(Some(format!(
+            "has no {EXPECTED_OUTPUT} yet, run `workshop lessons --update {}` once its output looks right",
+            lesson.number
+        )));
+    }
+    let golden = fs::read_to_string(&path)?;
+    if snapshot::matches(&golden, &run.stdout) {
+        return Ok(None);
+    }
+    Ok(Some(format!(
+        "printed something else than its {EXPECTED_OUTPUT}\n{}",
+        snapshot::describe(
+            &golden,
+            &run.stdout,
+            (EXPECTED_OUTPUT, "actual"),
+            term::use_color()
+        )
+    )))
+}
+
+// Rewrites the lesson's golden file with what it printed, if that changed.
+fn update(lesson: &Lesson, run: &Run) -> Result<bool> {
+    let path = lesson.dir.join(EXPECTED_OUTPUT);
+    let golden = fs::read_to_string(&path).unwrap_or_default();
+    let blessed = snapshot::bless(&golden, &run.stdout);
+    if path.is_file() && blessed == golden {
+        return Ok(false);
+    }
+    fs::write(&path, blessed)?;
+    Ok(true)
+}
+
+pub fn command(workspace: &Workspace, args: &[String]) -> Result<ExitCode> {
+    let args = Args::parse(args, &["--update"], &[])?;
+    let bless = args.flag("--update");
+    let selected = select(workspace, &args.positional())?;
+    let manifest = Manifest::load(workspace)?;
+
+    let mut rows = Vec::new();
+    let mut problems = Vec::new();
+    for lesson in &selected {
+        println!("{} {}...", term::paint("Running", Color::Cyan), lesson.name);
+        let run = run(workspace, lesson, manifest.lesson_input(&lesson.name))?;
+        let kind = match lesson.kind {
+            Kind::Rustc => "rustc",
+            Kind::Cargo(_) => "cargo",
+        };
+        // Only output from a lesson that ran fine is worth keeping.
+        let updated = bless && run.success && update(lesson, &run)?;
+        let result = match problem(lesson, &run)? {
+            None if updated => ("UPDATED".to_string(), Some(Color::Cyan)),
+            None => ("PASS".to_string(), Some(Color::Green)),
+            Some(problem) => {
+                problems.push((lesson, problem));
+                ("FAIL".to_string(), Some(Color::Red))
+            }
+        };
+        rows.push(vec![
+            (lesson.name.clone(), None),
+            (kind.to_string(), None),
+            result,
+        ]);
+    }
+
+    println!();
+    term::print_table(&["Lesson", "Built with", "Result"], &rows);
+    for (lesson, problem) in &problems {
+        println!("\n{}: {problem}", lesson.name);
+    }
+    Ok(if problems.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+pub fn mark_read(workspace: &Workspace, args: &[String]) -> Result<ExitCode> {
+    if args.is_empty() {
+        return Err("usage: workshop mark-read LESSON...".into());
+    }
+
+    let lessons = lessons(workspace)?;
+    let mut progress = Progress::load(workspace)?;
+    for query in args {
+        let lesson = find(&lessons, query)?;
+        progress.lessons.insert(lesson.name.clone());
+        println!("Marked {} as read", lesson.name);
+    }
+    progress.save(workspace)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lessons_in_order() {
+        let workspace = Workspace::ours();
+        let lessons = lessons(&workspace).unwrap();
+
+        let numbers: Vec<u32> = lessons.iter().map(|l| l.number).collect();
+        assert_eq!(numbers, (1..=9).collect::<Vec<_>>());
+
+        for query in ["4-ownership", "4", "ownership", "lessons/4-ownership/"] {
+            assert_eq!(find(&lessons, query).unwrap().name, "4-ownership");
+        }
+        assert!(find(&lessons, "10").is_err());
+    }
+
+    #[test]
+    fn hello_world_is_built_with_rustc() {
+        let workspace = Workspace::ours();
+        let lessons = lessons(&workspace).unwrap();
+        let lesson = find(&lessons, "1").unwrap();
+        assert!(matches!(lesson.kind, Kind::Rustc));
+        assert!(matches!(&find(&lessons, "2").unwrap().kind, Kind::Cargo(p) if p == "hello-cargo"));
+
+        let run = run(&workspace, lesson, None).unwrap();
+        assert_eq!(run.stdout, "Hello, world!\n");
+    }
+
+    #[test]
+    fn lessons_match_their_golden_files() {
+        let workspace = Workspace::ours();
+        let manifest = Manifest::load(&workspace).unwrap();
+        for lesson in lessons(&workspace).unwrap() {
+            let run = run(&workspace, &lesson, manifest.lesson_input(&lesson.name)).unwrap();
+            if let Some(problem) = problem(&lesson, &run).unwrap() {
+                panic!("{}: {problem}", lesson.name);
+            }
+        }
+    }
+}
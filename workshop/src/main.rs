@@ -0,0 +1,119 @@
+// The workshop runner. This is the one command to know when working through the
+// exercises: it finds every exercise listed in info.toml, builds it, and tells
+// you whether you're done.
+
+mod args;
+mod compile_fail;
+mod diff;
+mod grade;
+mod hint;
+mod instructor;
+mod json;
+mod lesson;
+mod manifest;
+mod progress;
+mod quiz;
+mod reader;
+mod report;
+mod run_section;
+mod scaffold;
+mod self_check;
+mod skeleton;
+mod slides;
+mod snapshot;
+mod status;
+mod temp;
+mod term;
+mod toml;
+mod watch;
+mod workspace;
+
+use std::env;
+use std::process::ExitCode;
+
+use workspace::Workspace;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+const USAGE: &str = "\
+Usage: workshop [COMMAND] [ARGS...]
+
+Commands:
+    grade [EXERCISE...]    Build and grade the exercises (the default)
+          [--json FILE]    Also write the results as a JSON report
+          [--junit FILE]   Also write the results as JUnit XML
+    watch [EXERCISE...]    Re-grade exercises whenever their sources change
+          [--interval MS]  How often to check for changes (default 500)
+    hint EXERCISE          Reveal the next hint for an exercise
+    status                 Show which lessons and exercises are done, in
+                           progress or still locked
+    mark-read LESSON...    Mark lessons as read, by name or number
+    read LESSON [SECTION]  Page through a lesson one section at a time
+         [--toc]           Only list the lesson's sections
+    run-section LESSON SECTION
+                [--show]   Run one section of a lesson on its own, or only
+                           show the program it's run as
+    quiz LESSON            Take the quiz on a lesson
+    quiz --check [LESSON...]
+                           Check that the quizzes are valid and their code runs
+    lessons [LESSON...]    Build and run the lessons, and check what they print
+            [--update]     Accept what they print now as the expected output
+    slides [LESSON...]     Make an HTML slide deck of each lesson
+           [--out DIR]     Where to write them (default target/slides)
+    generate [EXERCISE...] Generate the exercise skeletons from their solutions
+             [--check]     Only check that the skeletons are up to date
+    new-exercise NAME --lesson LESSON [--lib]
+                           Set up a new exercise crate, binary or library,
+                           and list it in info.toml
+    compile-fail [LESSON...]
+                           Check that the lessons' broken snippets fail to
+                           compile with the errors they claim
+    self-check             Check that every lesson and every reference
+                           solution works, skeletons aside
+    instructor report DIR  Summarize a cohort's progress, from one directory
+               [--html FILE]
+                           per student under DIR, optionally as a web page
+    help                   Print this message";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match run(&args) {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("{} {err}", term::paint("error:", term::Color::Red));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<ExitCode> {
+    let (command, rest) = match args.split_first() {
+        Some((command, rest)) => (command.as_str(), rest),
+        None => ("grade", args),
+    };
+
+    if matches!(command, "help" | "-h" | "--help") {
+        println!("{USAGE}");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let workspace = Workspace::discover(&env::current_dir()?)?;
+    match command {
+        "grade" => grade::command(&workspace, rest),
+        "watch" => watch::command(&workspace, rest),
+        "hint" => hint::command(&workspace, rest),
+        "status" => status::command(&workspace, rest),
+        "mark-read" => lesson::mark_read(&workspace, rest),
+        "read" => reader::command(&workspace, rest),
+        "run-section" => run_section::command(&workspace, rest),
+        "quiz" => quiz::command(&workspace, rest),
+        "lessons" => lesson::command(&workspace, rest),
+        "slides" => slides::command(&workspace, rest),
+        "generate" => skeleton::command(&workspace, rest),
+        "new-exercise" => scaffold::command(&workspace, rest),
+        "compile-fail" => compile_fail::command(&workspace, rest),
+        "self-check" => self_check::command(&workspace, rest),
+        "instructor" => instructor::command(&workspace, rest),
+        _ => Err(format!("unknown command `{command}`\n\n{USAGE}").into()),
+    }
+}
@@ -0,0 +1,299 @@
+// The exercise manifest, info.toml at the root of the workspace. It records what
+// the crates themselves can't: which order to do the exercises in, how each one
+// is graded, which lesson it belongs to, and hints for when you're stuck. It
+// also says what the lessons that read files or stdin should be given when
+// they're run non-interactively.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::grade::Mode;
+use crate::skeleton::SOLUTION;
+use crate::toml::{self, Table};
+use crate::workspace::{Crate, Workspace};
+use crate::Result;
+
+pub const FILE: &str = "info.toml";
+
+pub struct Manifest {
+    // In the order they appear in info.toml, which is the order to do them in
+    pub exercises: Vec<Exercise>,
+    pub lessons: Vec<LessonInput>,
+}
+
+pub struct Exercise {
+    pub name: String,
+    // Relative to the workspace root, e.g. "exercises/fibonacci"
+    pub path: PathBuf,
+    pub dir: PathBuf,
+    pub mode: Mode,
+    pub lesson: PathBuf,
+    // From a gentle nudge to almost the solution, see hint.rs
+    pub hints: Vec<String>,
+    // The file students edit, relative to the exercise crate. It's generated
+    // from the solution, see skeleton.rs.
+    pub skeleton: PathBuf,
+}
+
+// What a lesson is given when `workshop lessons` runs it
+pub struct LessonInput {
+    // The lesson's directory name, e.g. "3-nuts-and-bolts"
+    pub name: String,
+    // Relative to the lesson, each copied into the directory it runs in
+    pub fixtures: Vec<PathBuf>,
+    // Typed in as if by the student
+    pub stdin: Option<String>,
+}
+
+impl Manifest {
+    // Loads info.toml and checks it against the workspace, so that a typo in
+    // the manifest is caught here rather than as a confusing grading failure.
+    pub fn load(workspace: &Workspace) -> Result<Manifest> {
+        let path = workspace.root.join(FILE);
+        let text = fs::read_to_string(&path)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+        let table = toml::parse(&text).map_err(|err| format!("failed to parse {FILE}: {err}"))?;
+
+        let manifest = Manifest::from_table(&workspace.root, &table)?;
+        manifest.validate(workspace)?;
+        Ok(manifest)
+    }
+
+    fn from_table(root: &Path, table: &Table) -> Result<Manifest> {
+        let entries = table
+            .get("exercises")
+            .and_then(|e| e.as_array())
+            .ok_or_else(|| format!("{FILE} has no [[exercises]]"))?;
+
+        let mut exercises = Vec::new();
+        for (i, entry) in entries.iter().enumerate() {
+            let entry = entry
+                .as_table()
+                .ok_or_else(|| format!("{FILE}: exercise #{} is not a table", i + 1))?;
+            let field = |key: &str| -> Result<String> {
+                entry
+                    .get(key)
+                    .and_then(|v| v.as_str())
+                    .map(String::from)
+                    .ok_or_else(|| {
+                        format!("{FILE}: exercise #{} is missing the string `{key}`", i + 1).into()
+                    })
+            };
+
+            let name = field("name")?;
+            let mode = field("mode")?;
+            let mode = mode
+                .parse()
+                .map_err(|_| format!("{FILE}: `{name}` has an unknown mode `{mode}`"))?;
+            let path = PathBuf::from(field("path")?);
+            let hints: Vec<String> = entry
+                .get("hints")
+                .and_then(|h| h.as_array())
+                .and_then(|h| h.iter().map(|h| h.as_str().map(String::from)).collect())
+                .ok_or_else(|| format!("{FILE}: `{name}` needs a list of `hints`"))?;
+            if hints.is_empty() {
+                return Err(format!("{FILE}: `{name}` needs at least one hint").into());
+            }
+            let skeleton = match entry.get("skeleton") {
+                Some(value) => value.as_str().map(PathBuf::from).ok_or_else(|| {
+                    format!("{FILE}: `{name}` has a skeleton that isn't a string")
+                })?,
+                None => PathBuf::from("src/main.rs"),
+            };
+
+            exercises.push(Exercise {
+                dir: root.join(&path),
+                path,
+                mode,
+                lesson: PathBuf::from(field("lesson")?),
+                hints,
+                skeleton,
+                name,
+            });
+        }
+
+        let mut lessons = Vec::new();
+        let entries = table.get("lessons").and_then(|l| l.as_array());
+        for (i, entry) in entries.unwrap_or_default().iter().enumerate() {
+            let entry = entry
+                .as_table()
+                .ok_or_else(|| format!("{FILE}: lesson #{} is not a table", i + 1))?;
+            let name = entry
+                .get("name")
+                .and_then(|n| n.as_str())
+                .ok_or_else(|| format!("{FILE}: lesson #{} is missing the string `name`", i + 1))?
+                .to_string();
+            let fixtures = match entry.get("fixtures") {
+                None => Vec::new(),
+                Some(value) => value
+                    .as_array()
+                    .and_then(|f| f.iter().map(|f| f.as_str().map(PathBuf::from)).collect())
+                    .ok_or_else(|| format!("{FILE}: the fixtures of `{name}` must be paths"))?,
+            };
+            let stdin = match entry.get("stdin") {
+                None => None,
+                Some(value) => Some(
+                    value
+                        .as_str()
+                        .ok_or_else(|| format!("{FILE}: the stdin of `{name}` must be a string"))?
+                        .to_string(),
+                ),
+            };
+            lessons.push(LessonInput {
+                name,
+                fixtures,
+                stdin,
+            });
+        }
+
+        Ok(Manifest { exercises, lessons })
+    }
+
+    pub fn lesson_input(&self, lesson: &str) -> Option<&LessonInput> {
+        self.lessons.iter().find(|l| l.name == lesson)
+    }
+
+    // Collects every problem at once rather than stopping at the first, so they
+    // can all be fixed in one go.
+    pub fn validate(&self, workspace: &Workspace) -> Result<()> {
+        let mut problems = Vec::new();
+
+        for (i, exercise) in self.exercises.iter().enumerate() {
+            let name = &exercise.name;
+            if self.exercises[..i].iter().any(|e| &e.name == name) {
+                problems.push(format!("`{name}` is listed more than once"));
+            }
+
+            if !workspace.members.contains(&exercise.path) {
+                problems.push(format!(
+                    "`{name}` points at {}, which is not a workspace member",
+                    exercise.path.display()
+                ));
+            } else {
+                match Crate::load(&exercise.dir) {
+                    Ok(krate) if &krate.name != name => problems.push(format!(
+                        "`{name}` points at {}, but that crate is called `{}`",
+                        exercise.path.display(),
+                        krate.name
+                    )),
+                    Ok(krate) => {
+                        let solution = format!("{name}-solution");
+                        if exercise.mode == Mode::Output && !krate.bins.contains(&solution) {
+                            problems.push(format!(
+                                "`{name}` is graded against its solution, but has no `{solution}` binary"
+                            ));
+                        }
+                    }
+                    Err(err) => problems.push(format!("`{name}`: {err}")),
+                }
+            }
+
+            if matches!(exercise.mode, Mode::Assert | Mode::Output)
+                && !exercise.dir.join("src/main.rs").is_file()
+            {
+                problems.push(format!(
+                    "`{name}` is graded by running main, but has no src/main.rs"
+                ));
+            }
+
+            if !exercise.dir.join(SOLUTION).is_file() {
+                problems.push(format!("`{name}` has no solution at {SOLUTION}"));
+            }
+
+            if !workspace.root.join(&exercise.lesson).is_dir() {
+                problems.push(format!(
+                    "`{name}` belongs to {}, which does not exist",
+                    exercise.lesson.display()
+                ));
+            }
+        }
+
+        for member in workspace
+            .members
+            .iter()
+            .filter(|m| m.starts_with("exercises"))
+        {
+            if !self.exercises.iter().any(|e| &e.path == member) {
+                problems.push(format!(
+                    "{} is a workspace member but isn't listed",
+                    member.display()
+                ));
+            }
+        }
+
+        for lesson in &self.lessons {
+            let dir = workspace.root.join("lessons").join(&lesson.name);
+            if !dir.is_dir() {
+                problems.push(format!("there is no lesson `{}`", lesson.name));
+                continue;
+            }
+            for fixture in &lesson.fixtures {
+                if !dir.join(fixture).is_file() {
+                    problems.push(format!(
+                        "the fixture {} of `{}` does not exist",
+                        fixture.display(),
+                        lesson.name
+                    ));
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "{FILE} doesn't match the workspace:\n    {}",
+                problems.join("\n    ")
+            )
+            .into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_info_toml() {
+        let manifest = Manifest::load(&Workspace::ours()).unwrap();
+
+        let names: Vec<_> = manifest.exercises.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["fibonacci", "packages", "messages"]);
+
+        let messages = &manifest.exercises[2];
+        assert_eq!(messages.mode, Mode::Output);
+        assert_eq!(manifest.exercises[0].mode, Mode::Output);
+        assert_eq!(messages.lesson, PathBuf::from("lessons/7-enums"));
+
+        let input = manifest.lesson_input("3-nuts-and-bolts").unwrap();
+        assert_eq!(input.fixtures, [PathBuf::from("fixtures/names.txt")]);
+        assert!(input.stdin.as_deref().unwrap().ends_with("quit\n"));
+    }
+
+    #[test]
+    fn missing_member_fails_loudly() {
+        let mut workspace = Workspace::ours();
+        workspace
+            .members
+            .retain(|m| m != Path::new("exercises/packages"));
+
+        let manifest = Manifest::load(&workspace).err().unwrap().to_string();
+        assert!(manifest
+            .contains("`packages` points at exercises/packages, which is not a workspace member"));
+    }
+
+    #[test]
+    fn unknown_mode() {
+        let table = toml::parse(
+            "[[exercises]]\nname = \"x\"\npath = \"exercises/x\"\nmode = \"vibes\"\nlesson = \"lessons/x\"\nhints = []\n",
+        )
+        .unwrap();
+
+        let err = Manifest::from_table(Path::new("."), &table).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "info.toml: `x` has an unknown mode `vibes`"
+        );
+    }
+}
@@ -0,0 +1,209 @@
+// What the workshop remembers between runs, kept in .workshop/progress at the
+// root of the workspace: which lessons have been read, how the last grading of
+// each exercise went, how many hints were needed and how the last go at each
+// lesson's quiz went. It's a plain TOML file, so it's easy to inspect (or
+// reset) by hand.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::toml::{self, Table, Value};
+use crate::workspace::Workspace;
+use crate::Result;
+
+pub const FILE: &str = ".workshop/progress";
+
+#[derive(Default)]
+pub struct Progress {
+    // The lessons marked as read, by directory name
+    pub lessons: BTreeSet<String>,
+    // Whether the last grading of each exercise passed
+    pub exercises: BTreeMap<String, bool>,
+    // How many hints have been revealed, per exercise
+    pub hints: BTreeMap<String, usize>,
+    // The last quiz score per lesson, as (right answers, questions)
+    pub quizzes: BTreeMap<String, (usize, usize)>,
+}
+
+impl Progress {
+    // A missing file just means nothing has happened yet.
+    pub fn load(workspace: &Workspace) -> Result<Progress> {
+        let path = path(workspace);
+        if !path.exists() {
+            return Ok(Progress::default());
+        }
+        Progress::read(&path)
+    }
+
+    pub fn read(path: &Path) -> Result<Progress> {
+        let text = fs::read_to_string(path)?;
+        let table = toml::parse(&text)
+            .map_err(|err| format!("failed to parse {}: {err}", path.display()))?;
+        Progress::from_table(&table)
+    }
+
+    pub fn from_table(table: &Table) -> Result<Progress> {
+        let mut progress = Progress::default();
+        for (name, read) in section(table, "lessons")? {
+            let read = read
+                .as_bool()
+                .ok_or_else(|| format!("{FILE}: lesson `{name}` must be true or false"))?;
+            if read {
+                progress.lessons.insert(name.clone());
+            }
+        }
+        for (name, result) in section(table, "exercises")? {
+            let passed = match result.as_str() {
+                Some("passed") => true,
+                Some("failed") => false,
+                _ => {
+                    return Err(format!(
+                        "{FILE}: exercise `{name}` must be \"passed\" or \"failed\""
+                    )
+                    .into())
+                }
+            };
+            progress.exercises.insert(name.clone(), passed);
+        }
+        for (name, count) in section(table, "hints")? {
+            let count = count
+                .as_integer()
+                .and_then(|c| usize::try_from(c).ok())
+                .ok_or_else(|| format!("{FILE}: hints for `{name}` must be a count"))?;
+            progress.hints.insert(name.clone(), count);
+        }
+        for (name, score) in section(table, "quizzes")? {
+            let score = score
+                .as_str()
+                .and_then(|score| score.split_once('/'))
+                .and_then(|(right, total)| Some((right.parse().ok()?, total.parse().ok()?)))
+                .ok_or_else(|| format!("{FILE}: quiz score for `{name}` must be like \"3/5\""))?;
+            progress.quizzes.insert(name.clone(), score);
+        }
+        Ok(progress)
+    }
+
+    pub fn save(&self, workspace: &Workspace) -> Result<()> {
+        let path = path(workspace);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, toml::write(&self.to_table()))?;
+        Ok(())
+    }
+
+    pub fn to_table(&self) -> Table {
+        let lessons = self
+            .lessons
+            .iter()
+            .map(|name| (name.clone(), Value::Boolean(true)))
+            .collect();
+        let exercises = self
+            .exercises
+            .iter()
+            .map(|(name, &passed)| {
+                let result = if passed { "passed" } else { "failed" };
+                (name.clone(), Value::String(result.to_string()))
+            })
+            .collect();
+        let hints = self
+            .hints
+            .iter()
+            .map(|(name, &count)| (name.clone(), Value::Integer(count as i64)))
+            .collect();
+        let quizzes = self
+            .quizzes
+            .iter()
+            .map(|(name, (right, total))| (name.clone(), Value::String(format!("{right}/{total}"))))
+            .collect();
+
+        let mut table = Table::new();
+        table.insert("lessons".to_string(), Value::Table(lessons));
+        table.insert("exercises".to_string(), Value::Table(exercises));
+        table.insert("hints".to_string(), Value::Table(hints));
+        table.insert("quizzes".to_string(), Value::Table(quizzes));
+        table
+    }
+
+    pub fn hints_used(&self, exercise: &str) -> usize {
+        self.hints.get(exercise).copied().unwrap_or(0)
+    }
+
+    pub fn has_read(&self, lesson: &str) -> bool {
+        self.lessons.contains(lesson)
+    }
+
+    // None if the exercise has never been graded.
+    pub fn passed(&self, exercise: &str) -> Option<bool> {
+        self.exercises.get(exercise).copied()
+    }
+}
+
+// Remembers how grading an exercise went. This reloads the file first, so a
+// long-running `watch` doesn't clobber hints revealed in the meantime.
+pub fn record(workspace: &Workspace, exercise: &str, passed: bool) -> Result<()> {
+    let mut progress = Progress::load(workspace)?;
+    progress.exercises.insert(exercise.to_string(), passed);
+    progress.save(workspace)
+}
+
+// Remembers a quiz score, reloading first like `record` does.
+pub fn record_quiz(workspace: &Workspace, lesson: &str, right: usize, total: usize) -> Result<()> {
+    let mut progress = Progress::load(workspace)?;
+    progress.quizzes.insert(lesson.to_string(), (right, total));
+    progress.save(workspace)
+}
+
+fn path(workspace: &Workspace) -> PathBuf {
+    workspace.root.join(FILE)
+}
+
+fn section<'a>(table: &'a Table, name: &str) -> Result<&'a Table> {
+    static EMPTY: Table = Table::new();
+    match table.get(name) {
+        None => Ok(&EMPTY),
+        Some(value) => value
+            .as_table()
+            .ok_or_else(|| format!("{FILE}: `{name}` must be a table").into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_hint_counts() {
+        let table = toml::parse("[hints]\nfibonacci = 2\n").unwrap();
+        let progress = Progress::from_table(&table).unwrap();
+        assert_eq!(progress.hints_used("fibonacci"), 2);
+        assert_eq!(progress.hints_used("messages"), 0);
+
+        let table = toml::parse("[hints]\nfibonacci = -1\n").unwrap();
+        assert!(Progress::from_table(&table).is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut progress = Progress::default();
+        progress.lessons.insert("1-hello-world".to_string());
+        progress.exercises.insert("fibonacci".to_string(), true);
+        progress.exercises.insert("messages".to_string(), false);
+        progress.hints.insert("messages".to_string(), 2);
+        progress.quizzes.insert("4-ownership".to_string(), (3, 5));
+
+        let text = toml::write(&progress.to_table());
+        let read = Progress::from_table(&toml::parse(&text).unwrap()).unwrap();
+        assert!(read.has_read("1-hello-world"));
+        assert!(!read.has_read("2-hello-cargo"));
+        assert_eq!(read.passed("fibonacci"), Some(true));
+        assert_eq!(read.passed("messages"), Some(false));
+        assert_eq!(read.passed("packages"), None);
+        assert_eq!(read.hints_used("messages"), 2);
+        assert_eq!(read.quizzes.get("4-ownership"), Some(&(3, 5)));
+
+        let table = toml::parse("[exercises]\nfibonacci = \"done\"\n").unwrap();
+        assert!(Progress::from_table(&table).is_err());
+        let table = toml::parse("[quizzes]\n4-ownership = \"most\"\n").unwrap();
+        assert!(Progress::from_table(&table).is_err());
+    }
+}
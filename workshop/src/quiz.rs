@@ -0,0 +1,411 @@
+// `workshop quiz LESSON` quizzes you on a lesson, from the quiz.toml next to
+// it. There are two kinds of questions: multiple choice,
+//
+//     [[questions]]
+//     prompt = "What does `let b = a;` do to the String in `a`?"
+//     choices = ["Copies it", "Moves it into `b`"]
+//     answer = 2
+//     explanation = "Shown after answering, right or wrong (optional)"
+//
+// and "what does this print?", where the answer isn't written down anywhere:
+// the code is compiled and run, and you have to type what it printed.
+//
+//     [[questions]]
+//     prompt = "What does this print?"
+//     code = """
+//     let x = 5;
+//     println!("{x}");
+//     """
+//
+// Code without a main function is wrapped in one. The score of the last go at
+// each quiz is kept with the rest of the progress. `workshop quiz --check`
+// checks that every quiz file is valid and all of its code runs.
+
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::process::{Command, ExitCode, Stdio};
+
+use crate::args::Args;
+use crate::compile_fail::rustc;
+use crate::lesson::{self, Lesson};
+use crate::progress;
+use crate::reader;
+use crate::temp::TempDir;
+use crate::term::{self, Color};
+use crate::toml::{self, Value};
+use crate::workspace::Workspace;
+use crate::Result;
+
+pub const FILE: &str = "quiz.toml";
+
+pub struct Question {
+    pub prompt: String,
+    pub kind: Kind,
+    pub explanation: Option<String>,
+}
+
+pub enum Kind {
+    // `answer` counts from 0 here, though it counts from 1 in the file
+    Choice { choices: Vec<String>, answer: usize },
+    Output { code: String },
+}
+
+pub fn parse(text: &str) -> Result<Vec<Question>> {
+    let table = toml::parse(text)?;
+    let entries = table
+        .get("questions")
+        .and_then(Value::as_array)
+        .ok_or("no [[questions]]")?;
+
+    let mut questions = Vec::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let n = i + 1;
+        let entry = entry
+            .as_table()
+            .ok_or_else(|| format!("question {n} must be a table"))?;
+        let string = |key: &str| -> Result<Option<String>> {
+            match entry.get(key) {
+                None => Ok(None),
+                Some(value) => value
+                    .as_str()
+                    .map(|s| Some(s.to_string()))
+                    .ok_or_else(|| format!("question {n}: `{key}` must be a string").into()),
+            }
+        };
+        let prompt = string("prompt")?.ok_or_else(|| format!("question {n} has no prompt"))?;
+
+        let kind = match (entry.get("choices"), string("code")?) {
+            (Some(choices), None) => {
+                let choices = choices
+                    .as_array()
+                    .and_then(|choices| {
+                        choices
+                            .iter()
+                            .map(|c| c.as_str().map(str::to_string))
+                            .collect::<Option<Vec<_>>>()
+                    })
+                    .ok_or_else(|| format!("question {n}: `choices` must be a list of strings"))?;
+                let answer = entry
+                    .get("answer")
+                    .and_then(Value::as_integer)
+                    .and_then(|a| usize::try_from(a).ok())
+                    .filter(|a| (1..=choices.len()).contains(a))
+                    .ok_or_else(|| {
+                        format!("question {n}: `answer` must be the number of one of the choices")
+                    })?;
+                Kind::Choice {
+                    choices,
+                    answer: answer - 1,
+                }
+            }
+            (None, Some(code)) => Kind::Output { code },
+            _ => {
+                return Err(format!(
+                    "question {n} needs either `choices` and an `answer`, or `code`"
+                )
+                .into())
+            }
+        };
+        questions.push(Question {
+            prompt,
+            kind,
+            explanation: string("explanation")?,
+        });
+    }
+    Ok(questions)
+}
+
+// None if the lesson has no quiz.
+pub fn load(lesson: &Lesson) -> Result<Option<Vec<Question>>> {
+    let path = lesson.dir.join(FILE);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let text = fs::read_to_string(&path)?;
+    let questions =
+        parse(&text).map_err(|err| format!("{}: {err}", lesson.path.join(FILE).display()))?;
+    Ok(Some(questions))
+}
+
+// The whole program for a snippet of code.
+fn program(code: &str) -> String {
+    if code.contains("fn main(") {
+        return code.to_string();
+    }
+    let mut program = String::from("fn main() {\n");
+    for line in code.lines() {
+        if !line.is_empty() {
+            program.push_str("    ");
+        }
+        program.push_str(line);
+        program.push('\n');
+    }
+    program.push_str("}\n");
+    program
+}
+
+// What the code prints. It failing to compile or run is the quiz's fault, not
+// yours, so that's an error.
+pub fn output_of(code: &str) -> Result<String> {
+    let dir = TempDir::new("quiz")?;
+    let source = dir.path().join("main.rs");
+    let binary = dir.path().join("main");
+    fs::write(&source, program(code))?;
+    let build = Command::new(rustc())
+        .args(["--edition", "2021", "-A", "warnings", "-o"])
+        .arg(&binary)
+        .arg(&source)
+        .output()
+        .map_err(|err| format!("failed to run rustc: {err}"))?;
+    if !build.status.success() {
+        return Err(format!(
+            "the code doesn't compile\n{}",
+            String::from_utf8_lossy(&build.stderr).trim_end()
+        )
+        .into());
+    }
+    let run = Command::new(&binary)
+        .current_dir(dir.path())
+        .env("RUST_BACKTRACE", "0")
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|err| format!("failed to run the code: {err}"))?;
+    if !run.status.success() {
+        return Err(format!(
+            "the code doesn't run successfully\n{}",
+            String::from_utf8_lossy(&run.stderr).trim_end()
+        )
+        .into());
+    }
+    Ok(String::from_utf8_lossy(&run.stdout).into_owned())
+}
+
+// Whether an answer says the same as the output, give or take whitespace
+// around each line and blank lines at the end.
+fn same_output(output: &str, answer: &str) -> bool {
+    let lines = |text: &str| {
+        let mut lines: Vec<String> = text.lines().map(|l| l.trim().to_string()).collect();
+        while lines.last().is_some_and(String::is_empty) {
+            lines.pop();
+        }
+        lines
+    };
+    lines(output) == lines(answer)
+}
+
+fn check(workspace: &Workspace, queries: &[&str]) -> Result<ExitCode> {
+    let mut failed = false;
+    for lesson in &lesson::select(workspace, queries)? {
+        let questions = match load(lesson) {
+            Ok(Some(questions)) => questions,
+            Ok(None) => continue,
+            Err(err) => {
+                println!("{} {err}", term::paint("FAIL", Color::Red));
+                failed = true;
+                continue;
+            }
+        };
+        let mut problems = Vec::new();
+        for (i, question) in questions.iter().enumerate() {
+            if let Kind::Output { code } = &question.kind {
+                if let Err(err) = output_of(code) {
+                    problems.push(format!("question {}: {err}", i + 1));
+                }
+            }
+        }
+        if problems.is_empty() {
+            println!(
+                "{} {} ({} questions)",
+                term::paint("ok", Color::Green),
+                lesson.name,
+                questions.len()
+            );
+        } else {
+            failed = true;
+            println!("{} {}", term::paint("FAIL", Color::Red), lesson.name);
+            for problem in problems {
+                println!("{problem}");
+            }
+        }
+    }
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+// Prompts until there's a line of input, or None at the end of it.
+fn ask(
+    input: &mut impl Iterator<Item = io::Result<String>>,
+    prompt: &str,
+) -> Result<Option<String>> {
+    print!("{prompt}");
+    io::stdout().flush()?;
+    Ok(input.next().transpose()?)
+}
+
+pub fn command(workspace: &Workspace, args: &[String]) -> Result<ExitCode> {
+    let usage = "usage: workshop quiz LESSON, or workshop quiz --check [LESSON...]";
+    let args = Args::parse(args, &["--check"], &[])?;
+    let queries = args.positional();
+    if args.flag("--check") {
+        return check(workspace, &queries);
+    }
+    let [query] = queries[..] else {
+        return Err(usage.into());
+    };
+
+    let lessons = lesson::lessons(workspace)?;
+    let lesson = lesson::find(&lessons, query)?;
+    let questions = load(lesson)?.ok_or_else(|| {
+        format!(
+            "{} has no quiz, there's no {}",
+            lesson.name,
+            lesson.path.join(FILE).display()
+        )
+    })?;
+
+    println!(
+        "{}",
+        term::paint(
+            &format!("Quiz on {} ({} questions)", lesson.name, questions.len()),
+            Color::Bold
+        )
+    );
+    let color = term::use_color();
+    let mut input = io::stdin().lock().lines();
+    let mut correct = 0;
+    for (i, question) in questions.iter().enumerate() {
+        println!("\n{}. {}", i + 1, question.prompt);
+        let right = match &question.kind {
+            Kind::Choice { choices, answer } => {
+                for (j, choice) in choices.iter().enumerate() {
+                    println!("   {}) {choice}", j + 1);
+                }
+                let picked = loop {
+                    let Some(line) = ask(&mut input, "> ")? else {
+                        println!("\nQuiz abandoned, nothing recorded.");
+                        return Ok(ExitCode::FAILURE);
+                    };
+                    match line.trim().parse::<usize>() {
+                        Ok(n) if (1..=choices.len()).contains(&n) => break n - 1,
+                        _ => println!("Pick a number from 1 to {}.", choices.len()),
+                    }
+                };
+                if picked == *answer {
+                    true
+                } else {
+                    println!("The answer is {}) {}", answer + 1, choices[*answer]);
+                    false
+                }
+            }
+            Kind::Output { code } => {
+                println!();
+                for line in code.lines() {
+                    println!("    {}", reader::highlight(line, color));
+                }
+                let output = output_of(code)
+                    .map_err(|err| format!("question {} of {}: {err}", i + 1, lesson.name))?;
+                let count = output.lines().count();
+                println!();
+                if count > 1 {
+                    println!("It prints {count} lines, type them one by one.");
+                }
+                let mut answer = String::new();
+                for _ in 0..count.max(1) {
+                    let Some(line) = ask(&mut input, "> ")? else {
+                        println!("\nQuiz abandoned, nothing recorded.");
+                        return Ok(ExitCode::FAILURE);
+                    };
+                    answer.push_str(&line);
+                    answer.push('\n');
+                }
+                if same_output(&output, &answer) {
+                    true
+                } else {
+                    println!("It prints:\n{}", output.trim_end());
+                    false
+                }
+            }
+        };
+        if right {
+            correct += 1;
+            println!("{}", term::paint("Right!", Color::Green));
+        } else {
+            println!("{}", term::paint("Not quite.", Color::Red));
+        }
+        if let Some(explanation) = &question.explanation {
+            println!("{}", term::paint(explanation, Color::Dim));
+        }
+    }
+
+    println!(
+        "\n{}",
+        term::paint(
+            &format!("You got {correct}/{} right.", questions.len()),
+            Color::Bold
+        )
+    );
+    progress::record_quiz(workspace, &lesson.name, correct, questions.len())?;
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_questions() {
+        let questions = parse(
+            r#"
+[[questions]]
+prompt = "Pick two"
+choices = ["one", "two"]
+answer = 2
+
+[[questions]]
+prompt = "What does this print?"
+code = "println!(\"hi\");"
+explanation = "It says hi."
+"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            &questions[0].kind,
+            Kind::Choice { choices, answer: 1 } if choices.len() == 2
+        ));
+        assert!(matches!(&questions[1].kind, Kind::Output { .. }));
+        assert_eq!(questions[1].explanation.as_deref(), Some("It says hi."));
+
+        let err = parse("[[questions]]\nprompt = \"?\"\nchoices = [\"a\"]\nanswer = 2\n");
+        assert!(err.is_err_and(|err| err.to_string().contains("question 1")));
+        assert!(parse("[[questions]]\nprompt = \"?\"\n").is_err());
+    }
+
+    #[test]
+    fn compare_output() {
+        assert_eq!(
+            program("let x = 1;\n\nx;\n"),
+            "fn main() {\n    let x = 1;\n\n    x;\n}\n"
+        );
+        assert_eq!(program("fn main() {}\n"), "fn main() {}\n");
+        assert!(same_output("a\n  b\n", "a  \nb\n\n"));
+        assert!(!same_output("a\nb\n", "a\n"));
+    }
+
+    #[test]
+    fn lesson_quizzes_are_valid() {
+        let workspace = Workspace::ours();
+        let lessons = lesson::lessons(&workspace).unwrap();
+        for query in ["4", "8"] {
+            let lesson = lesson::find(&lessons, query).unwrap();
+            let questions = load(lesson).unwrap().expect("lesson has a quiz");
+            for question in questions {
+                if let Kind::Output { code } = &question.kind {
+                    output_of(code).unwrap();
+                }
+            }
+        }
+    }
+}
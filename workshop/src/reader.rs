@@ -0,0 +1,459 @@
+// `workshop read LESSON` pages through a lesson one section at a time. The
+// lessons are split into sections by banners like
+//
+//     // ******************** References and Borrowing ********************
+//
+// and anything before the first banner is the introduction. Within a section,
+// comments are shown as the prose they are, and the code is highlighted. Paging
+// to the last section marks the lesson as read.
+
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::process::ExitCode;
+
+use crate::args::Args;
+use crate::lesson::{self, Lesson};
+use crate::progress::Progress;
+use crate::term::{self, Color};
+use crate::workspace::Workspace;
+use crate::Result;
+
+const KEYWORDS: &[&str] = &[
+    "as", "break", "const", "continue", "crate", "dyn", "else", "enum", "false", "fn", "for", "if",
+    "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self",
+    "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where",
+    "while",
+];
+
+pub struct Section {
+    pub title: String,
+    // The section's source lines, without the banner
+    pub lines: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Block {
+    // A paragraph of comments, with the `//`s taken off
+    Prose(Vec<String>),
+    Code(Vec<String>),
+}
+
+pub fn sections(source: &str) -> Vec<Section> {
+    let mut sections = vec![Section {
+        title: "Introduction".to_string(),
+        lines: Vec::new(),
+    }];
+    for line in source.lines() {
+        match banner(line) {
+            Some(title) => sections.push(Section {
+                title,
+                lines: Vec::new(),
+            }),
+            None => sections.last_mut().unwrap().lines.push(line.to_string()),
+        }
+    }
+    // A lesson that starts right away with a banner has no introduction.
+    if sections.len() > 1 && sections[0].lines.iter().all(|l| l.trim().is_empty()) {
+        sections.remove(0);
+    }
+    sections
+}
+
+pub fn banner(line: &str) -> Option<String> {
+    let title = line
+        .trim()
+        .strip_prefix("//")?
+        .trim()
+        .strip_prefix("***")?
+        .strip_suffix("***")?
+        .trim_matches('*')
+        .trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+impl Section {
+    pub fn blocks(&self) -> Vec<Block> {
+        // Code is shown relative to the least indented line of the section,
+        // not counting the brace that closes main at the end of the last one.
+        let indent = self
+            .lines
+            .iter()
+            .filter(|line| !matches!(line.trim(), "" | "}"))
+            .map(|line| line.len() - line.trim_start().len())
+            .min()
+            .unwrap_or(0);
+
+        let mut blocks = Vec::new();
+        let mut blank = false;
+        // How many braces opened in commented-out code are still open, so the
+        // rest of a commented-out `enum` stays with its first line.
+        let mut depth = 0;
+        for line in &self.lines {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                blank = true;
+                continue;
+            }
+            let mut prose = trimmed
+                .strip_prefix("//")
+                .filter(|rest| !rest.starts_with('/'));
+            if let Some(comment) = prose.filter(|c| depth > 0 || is_code(c)) {
+                depth += comment.matches('{').count() as i32;
+                depth = (depth - comment.matches('}').count() as i32).max(0);
+                prose = None;
+            }
+            match (prose, blocks.last_mut()) {
+                (Some(text), Some(Block::Prose(lines))) if !blank => {
+                    lines.push(text.trim().to_string());
+                }
+                (Some(text), _) => blocks.push(Block::Prose(vec![text.trim().to_string()])),
+                (None, Some(Block::Code(lines))) => {
+                    // Blank lines within code are part of it.
+                    if blank {
+                        lines.push(String::new());
+                    }
+                    lines.push(line.get(indent..).unwrap_or(trimmed).to_string());
+                }
+                (None, _) => blocks.push(Block::Code(vec![line
+                    .get(indent..)
+                    .unwrap_or(trimmed)
+                    .to_string()])),
+            }
+            blank = false;
+        }
+        blocks
+    }
+}
+
+// Whether a comment is commented-out code rather than prose, like
+// `// println!("{a} {b}"); // a and b have been moved`.
+fn is_code(comment: &str) -> bool {
+    let code = comment.split(" //").next().unwrap_or(comment).trim();
+    code.ends_with([';', '{', '}']) || (code.contains("!(") && code.ends_with(')'))
+}
+
+// Splits a line of code into the pieces to highlight, with their colors:
+// keywords, string literals and trailing comments.
+pub fn tokens(line: &str) -> Vec<(String, Option<Color>)> {
+    let mut tokens: Vec<(String, Option<Color>)> = Vec::new();
+    let mut push = |text: String, color: Option<Color>| match tokens.last_mut() {
+        Some((last, None)) if color.is_none() => last.push_str(&text),
+        _ => tokens.push((text, color)),
+    };
+    let chars: Vec<char> = line.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '/' && chars.get(i + 1) == Some(&'/') {
+            push(chars[i..].iter().collect(), Some(Color::Dim));
+            break;
+        } else if c == '"' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            let end = (i + 1).min(chars.len());
+            push(chars[start..end].iter().collect(), Some(Color::Green));
+            i = end;
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            let color = KEYWORDS.contains(&word.as_str()).then_some(Color::Cyan);
+            push(word, color);
+        } else {
+            push(c.to_string(), None);
+            i += 1;
+        }
+    }
+    tokens
+}
+
+pub fn highlight(line: &str, color: bool) -> String {
+    tokens(line)
+        .into_iter()
+        .map(|(text, c)| match c {
+            Some(c) => term::paint_with(&text, c, color),
+            None => text,
+        })
+        .collect()
+}
+
+pub fn render(lesson: &Lesson, sections: &[Section], index: usize, color: bool) -> String {
+    let section = &sections[index];
+    let title = format!(
+        "{} - {}/{}: {}",
+        lesson.name,
+        index + 1,
+        sections.len(),
+        section.title
+    );
+    let mut out = format!("{}\n", term::paint_with(&title, Color::Bold, color));
+    for block in section.blocks() {
+        out.push('\n');
+        match block {
+            Block::Prose(lines) => {
+                for line in lines {
+                    out.push_str(&format!("{line}\n"));
+                }
+            }
+            Block::Code(lines) => {
+                for line in lines {
+                    if !line.is_empty() {
+                        out.push_str(&format!("    {}", highlight(&line, color)));
+                    }
+                    out.push('\n');
+                }
+            }
+        }
+    }
+    out
+}
+
+pub fn table_of_contents(lesson: &Lesson, sections: &[Section], current: Option<usize>) -> String {
+    let mut out = format!("{}\n", lesson.name);
+    for (i, section) in sections.iter().enumerate() {
+        let marker = if current == Some(i) { ">" } else { " " };
+        out.push_str(&format!("{marker} {:>2}. {}\n", i + 1, section.title));
+    }
+    out
+}
+
+// A section by its number (counting from 1) or its title.
+pub fn find_section(sections: &[Section], query: &str) -> Result<usize> {
+    if let Ok(number) = query.parse::<usize>() {
+        if (1..=sections.len()).contains(&number) {
+            return Ok(number - 1);
+        }
+    }
+    sections
+        .iter()
+        .position(|s| s.title.eq_ignore_ascii_case(query))
+        .ok_or_else(|| format!("no section called `{query}`").into())
+}
+
+pub fn load(lesson: &Lesson) -> Result<Vec<Section>> {
+    let path = lesson.source();
+    let source = fs::read_to_string(&path)
+        .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+    Ok(sections(&source))
+}
+
+pub fn command(workspace: &Workspace, args: &[String]) -> Result<ExitCode> {
+    let usage = "usage: workshop read LESSON [SECTION] [--toc]";
+    let args = Args::parse(args, &["--toc"], &[])?;
+    let toc = args.flag("--toc");
+    let (lesson, section) = match args.positional()[..] {
+        [lesson] => (lesson, None),
+        [lesson, section] => (lesson, Some(section)),
+        _ => return Err(usage.into()),
+    };
+
+    let lessons = lesson::lessons(workspace)?;
+    let lesson = lesson::find(&lessons, lesson)?;
+    let sections = load(lesson)?;
+    if toc {
+        print!("{}", table_of_contents(lesson, &sections, None));
+        return Ok(ExitCode::SUCCESS);
+    }
+    let mut index = match section {
+        Some(query) => find_section(&sections, query)?,
+        None => 0,
+    };
+
+    // Without someone at the keyboard, just show the one section.
+    let color = term::use_color();
+    if !io::stdin().is_terminal() {
+        print!("{}", render(lesson, &sections, index, color));
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let mut input = io::stdin().lock().lines();
+    loop {
+        if color {
+            // Clear the screen and move the cursor to the top left.
+            print!("\x1b[2J\x1b[H");
+        }
+        print!("{}", render(lesson, &sections, index, color));
+        if index + 1 == sections.len() {
+            let mut progress = Progress::load(workspace)?;
+            if progress.lessons.insert(lesson.name.clone()) {
+                progress.save(workspace)?;
+                println!(
+                    "\n{}",
+                    term::paint(
+                        &format!("That's the end of {}, marked as read.", lesson.name),
+                        Color::Green
+                    )
+                );
+            }
+        }
+
+        loop {
+            print!(
+                "\n{} ",
+                term::paint(
+                    "[n]ext, [p]revious, [t]able of contents, a section number, or [q]uit:",
+                    Color::Dim
+                )
+            );
+            io::stdout().flush()?;
+            let Some(line) = input.next() else {
+                return Ok(ExitCode::SUCCESS);
+            };
+            match line?.trim() {
+                "" | "n" | "next" if index + 1 < sections.len() => index += 1,
+                "" | "n" | "next" => {
+                    println!("That was the last section.");
+                    continue;
+                }
+                "p" | "prev" | "previous" if index > 0 => index -= 1,
+                "p" | "prev" | "previous" => {
+                    println!("This is the first section.");
+                    continue;
+                }
+                "t" | "toc" => {
+                    print!("\n{}", table_of_contents(lesson, &sections, Some(index)));
+                    continue;
+                }
+                "q" | "quit" => return Ok(ExitCode::SUCCESS),
+                other => match find_section(&sections, other) {
+                    Ok(i) => index = i,
+                    Err(err) => {
+                        println!("{err}");
+                        continue;
+                    }
+                },
+            }
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_banners() {
+        assert_eq!(
+            banner("    // ******************** The \"str\" Type ********************").as_deref(),
+            Some("The \"str\" Type")
+        );
+        assert_eq!(banner("// just a comment"), None);
+        assert_eq!(banner("// ****"), None);
+
+        let workspace = Workspace::ours();
+        let lessons = lesson::lessons(&workspace).unwrap();
+        let sections = load(lesson::find(&lessons, "4").unwrap()).unwrap();
+        let titles: Vec<&str> = sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            [
+                "Introduction",
+                "The \"str\" Type",
+                "Extendable Strings",
+                "To Move or Not to Move",
+                "References and Borrowing"
+            ]
+        );
+        assert_eq!(find_section(&sections, "extendable strings").unwrap(), 2);
+        assert_eq!(find_section(&sections, "5").unwrap(), 4);
+        assert!(find_section(&sections, "6").is_err());
+    }
+
+    #[test]
+    fn split_prose_from_code() {
+        let sections = sections(
+            "\
+fn main() {
+    // ******** Scope ********
+
+    // Variables live until the end of
+    // their scope.
+
+    // Like this:
+    let x = 5; // x is valid
+
+    println!(\"{x}\");
+}
+",
+        );
+        assert_eq!(sections.len(), 2);
+        assert_eq!(
+            sections[1].blocks(),
+            [
+                Block::Prose(vec![
+                    "Variables live until the end of".to_string(),
+                    "their scope.".to_string()
+                ]),
+                Block::Prose(vec!["Like this:".to_string()]),
+                Block::Code(vec![
+                    "let x = 5; // x is valid".to_string(),
+                    String::new(),
+                    "println!(\"{x}\");".to_string(),
+                    "}".to_string()
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn keep_commented_out_code() {
+        let sections = sections(
+            "\
+fn main() {
+    // ******** Moves ********
+
+    // After a move the old name can't be used:
+    // println!(\"{a} {b}\"); // a and b have been moved
+
+    // enum IpAddr {
+    //     V4(u8, u8, u8, u8),
+    // }
+}
+",
+        );
+        assert_eq!(
+            sections[1].blocks(),
+            [
+                Block::Prose(vec!["After a move the old name can't be used:".to_string()]),
+                Block::Code(vec![
+                    "// println!(\"{a} {b}\"); // a and b have been moved".to_string(),
+                    String::new(),
+                    "// enum IpAddr {".to_string(),
+                    "//     V4(u8, u8, u8, u8),".to_string(),
+                    "// }".to_string(),
+                    "}".to_string()
+                ]),
+            ]
+        );
+        assert!(!is_code(" complex (and costly)"));
+    }
+
+    #[test]
+    fn highlight_code() {
+        let line = "let s = \"fn \\\"x\\\"\"; // mut";
+        assert_eq!(highlight(line, false), line);
+        assert_eq!(
+            tokens(line),
+            [
+                ("let".to_string(), Some(Color::Cyan)),
+                (" s = ".to_string(), None),
+                ("\"fn \\\"x\\\"\"".to_string(), Some(Color::Green)),
+                ("; ".to_string(), None),
+                ("// mut".to_string(), Some(Color::Dim)),
+            ]
+        );
+        assert_eq!(
+            highlight(line, true),
+            "\x1b[36mlet\x1b[0m s = \x1b[32m\"fn \\\"x\\\"\"\x1b[0m; \x1b[2m// mut\x1b[0m"
+        );
+    }
+}
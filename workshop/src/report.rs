@@ -0,0 +1,252 @@
+// Grading results in formats other tools understand, for instructors who want
+// to collect them from a whole cohort: a JSON report, and a JUnit XML file that
+// any test report viewer can show.
+
+use std::time::Duration;
+
+use crate::compile_fail;
+use crate::grade::Outcome;
+use crate::json::{self, Value};
+use crate::manifest::Exercise;
+
+pub fn json(results: &[(&Exercise, &Outcome)]) -> String {
+    let passed = results.iter().filter(|(_, o)| o.passed).count();
+    let exercises = results
+        .iter()
+        .map(|(exercise, outcome)| {
+            let mut fields = vec![
+                ("name".to_string(), exercise.name.as_str().into()),
+                ("mode".to_string(), exercise.mode.to_string().into()),
+                (
+                    "lesson".to_string(),
+                    exercise.lesson.display().to_string().into(),
+                ),
+                ("passed".to_string(), outcome.passed.into()),
+                ("elapsed".to_string(), seconds(outcome.elapsed).into()),
+                (
+                    "failing_tests".to_string(),
+                    strings(failing_tests(&outcome.output)),
+                ),
+                (
+                    "error_codes".to_string(),
+                    strings(compile_fail::error_codes(&outcome.output)),
+                ),
+            ];
+            if let Some(problem) = outcome.first_problem(false) {
+                fields.push(("problem".to_string(), problem.into()));
+            }
+            fields.push(("diagnostics".to_string(), outcome.output.as_str().into()));
+            Value::Object(fields)
+        })
+        .collect();
+
+    json::write(&Value::Object(vec![
+        ("passed".to_string(), passed.into()),
+        ("total".to_string(), results.len().into()),
+        ("exercises".to_string(), Value::Array(exercises)),
+    ]))
+}
+
+// One test case per exercise, all in a single suite.
+pub fn junit(results: &[(&Exercise, &Outcome)]) -> String {
+    let failures = results.iter().filter(|(_, o)| !o.passed).count();
+    let time: Duration = results.iter().map(|(_, o)| o.elapsed).sum();
+    let counts = format!(
+        "tests=\"{}\" failures=\"{failures}\" time=\"{:.3}\"",
+        results.len(),
+        time.as_secs_f64()
+    );
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!("<testsuites name=\"workshop\" {counts}>\n"));
+    out.push_str(&format!(
+        "  <testsuite name=\"workshop\" {counts} errors=\"0\">\n"
+    ));
+    for (exercise, outcome) in results {
+        let open = format!(
+            "    <testcase name=\"{}\" classname=\"workshop.{}\" time=\"{:.3}\"",
+            escape_xml(&exercise.name),
+            exercise.mode,
+            outcome.elapsed.as_secs_f64()
+        );
+        if outcome.passed {
+            out.push_str(&format!("{open}/>\n"));
+            continue;
+        }
+
+        let problem = outcome.first_problem(false).unwrap_or_default();
+        let message = problem.lines().next().unwrap_or("failed");
+        // A mismatch's diff isn't part of cargo's output, so it goes first.
+        let body = match &outcome.mismatch {
+            Some(_) => format!("{problem}\n\n{}", outcome.output),
+            None => outcome.output.clone(),
+        };
+        out.push_str(&format!("{open}>\n"));
+        out.push_str(&format!(
+            "      <failure message=\"{}\" type=\"{}\">{}</failure>\n",
+            escape_xml(message),
+            failure_kind(outcome),
+            escape_xml(&body)
+        ));
+        out.push_str("    </testcase>\n");
+    }
+    out.push_str("  </testsuite>\n</testsuites>\n");
+    out
+}
+
+fn failure_kind(outcome: &Outcome) -> &'static str {
+    if outcome.mismatch.is_some() {
+        "output mismatch"
+    } else if outcome.output.contains("error: could not compile") {
+        "compile error"
+    } else if !failing_tests(&outcome.output).is_empty() {
+        "test failure"
+    } else {
+        "panic"
+    }
+}
+
+// The names of the tests cargo reported as failed, from the list libtest ends a
+// failed run with (`cargo test --quiet` doesn't print `test NAME ... FAILED`):
+//
+//     failures:
+//         package::tests::calculate_transport_fees
+//
+// The `failures:` before the tests' output is followed by a blank line instead.
+// There's a list for each test binary that failed.
+pub fn failing_tests(output: &str) -> Vec<String> {
+    let mut tests = Vec::new();
+    let mut lines = output.lines();
+    while let Some(line) = lines.next() {
+        if line == "failures:" {
+            let names = lines.by_ref().map_while(|line| line.strip_prefix("    "));
+            tests.extend(names.map(String::from));
+        }
+    }
+    tests
+}
+
+pub fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // XML can't hold other control characters at all, even escaped.
+            '\n' | '\t' | '\r' => out.push(c),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn seconds(duration: Duration) -> f64 {
+    (duration.as_secs_f64() * 1000.0).round() / 1000.0
+}
+
+fn strings(values: Vec<String>) -> Value {
+    Value::Array(values.into_iter().map(Value::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grade::FAILED_TESTS_OUTPUT;
+    use crate::workspace::Workspace;
+
+    fn results() -> Vec<(Exercise, Outcome)> {
+        let workspace = Workspace::ours();
+        let manifest = crate::manifest::Manifest::load(&workspace).unwrap();
+        let mut exercises = manifest.exercises.into_iter();
+        let fibonacci = exercises.next().unwrap();
+        let packages = exercises.next().unwrap();
+        vec![
+            (
+                fibonacci,
+                Outcome {
+                    passed: true,
+                    output: "0\n1\n".to_string(),
+                    mismatch: None,
+                    elapsed: Duration::from_millis(1250),
+                },
+            ),
+            (
+                packages,
+                Outcome {
+                    passed: false,
+                    output: FAILED_TESTS_OUTPUT.to_string(),
+                    mismatch: None,
+                    elapsed: Duration::from_millis(500),
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn parse_failing_tests() {
+        let (_, outcome) = &results()[1];
+        assert_eq!(
+            failing_tests(&outcome.output),
+            [
+                "package::tests::calculate_transport_fees",
+                "package::tests::create_international_package",
+                "package::tests::create_local_package",
+            ]
+        );
+        // Verbose runs end with the same list.
+        let verbose = "\
+test tests::create_package ... ok
+test tests::calculate_transport_fees ... FAILED
+
+failures:
+
+---- tests::calculate_transport_fees stdout ----
+
+thread 'tests::calculate_transport_fees' panicked at src/tests.rs:46:5:
+assertion failed: false
+
+
+failures:
+    tests::calculate_transport_fees
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out
+";
+        assert_eq!(failing_tests(verbose), ["tests::calculate_transport_fees"]);
+        assert!(failing_tests("0\n1\n1\n").is_empty());
+    }
+
+    #[test]
+    fn json_report() {
+        let results = results();
+        let results: Vec<_> = results.iter().map(|(e, o)| (e, o)).collect();
+        let report = json(&results);
+        assert!(report.starts_with("{\n  \"passed\": 1,\n  \"total\": 2,\n"));
+        assert!(report.contains("\"elapsed\": 1.25,"));
+        assert!(report.contains(
+            "\"failing_tests\": [\n        \"package::tests::calculate_transport_fees\",\n"
+        ));
+    }
+
+    #[test]
+    fn junit_report() {
+        let results = results();
+        let results: Vec<_> = results.iter().map(|(e, o)| (e, o)).collect();
+        let report = junit(&results);
+        assert!(report.contains(
+            "<testcase name=\"fibonacci\" classname=\"workshop.output\" time=\"1.250\"/>"
+        ));
+        assert!(report.contains(
+            "<failure message=\"thread &apos;package::tests::calculate_transport_fees&apos; (31301) panicked at exercises/packages/src/tests.rs:41:5:\" type=\"test failure\">"
+        ));
+        assert!(report.contains("assertion failed: !package.is_international()"));
+        assert!(report.contains("assertion `left == right` failed"));
+        assert_eq!(
+            escape_xml("<a & \"b\">\u{7}"),
+            "&lt;a &amp; &quot;b&quot;&gt;"
+        );
+    }
+}
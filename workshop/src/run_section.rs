@@ -0,0 +1,305 @@
+// `workshop run-section LESSON SECTION` runs a single section of a lesson, so
+// you can see what that one part prints (and play with it) without running
+// everything before it.
+//
+// The section is cut out of the lesson's main function and put in a main of its
+// own, in a crate under target/sections/. Anything the section might need from
+// the rest of the lesson comes along: the code outside of main, and the
+// functions, types and `use`s declared in main's other sections. Variables from
+// other sections don't, so a section that uses one won't compile on its own.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, ExitCode};
+
+use crate::args::Args;
+use crate::grade::cargo;
+use crate::lesson;
+use crate::manifest::Manifest;
+use crate::reader;
+use crate::temp::TempDir;
+use crate::term::{self, Color};
+use crate::workspace::Workspace;
+use crate::Result;
+
+// How a line within main can start if it begins an item rather than a statement
+const ITEMS: &[&str] = &[
+    "#[", "const ", "enum ", "fn ", "impl ", "impl<", "mod ", "pub ", "static ", "struct ",
+    "trait ", "type ", "use ",
+];
+
+// Counts how deep in braces we are, line by line, skipping the braces in
+// comments and in string and character literals.
+#[derive(Default)]
+struct Braces {
+    depth: i32,
+    // How many block comments deep we are, they can be nested
+    comment: usize,
+}
+
+impl Braces {
+    fn scan(&mut self, line: &str) {
+        let chars: Vec<char> = line.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            let next = chars.get(i + 1).copied();
+            if self.comment > 0 {
+                match (chars[i], next) {
+                    ('*', Some('/')) => {
+                        self.comment -= 1;
+                        i += 1;
+                    }
+                    ('/', Some('*')) => {
+                        self.comment += 1;
+                        i += 1;
+                    }
+                    _ => {}
+                }
+                i += 1;
+                continue;
+            }
+            match (chars[i], next) {
+                ('/', Some('/')) => return,
+                ('/', Some('*')) => {
+                    self.comment += 1;
+                    i += 1;
+                }
+                ('"', _) => {
+                    i += 1;
+                    while i < chars.len() && chars[i] != '"' {
+                        if chars[i] == '\\' {
+                            i += 1;
+                        }
+                        i += 1;
+                    }
+                }
+                // A character literal, rather than a lifetime
+                ('\'', Some('\\')) => {
+                    i += 2;
+                    while i < chars.len() && chars[i] != '\'' {
+                        i += 1;
+                    }
+                }
+                ('\'', Some(_)) if chars.get(i + 2) == Some(&'\'') => i += 2,
+                ('{', _) => self.depth += 1,
+                ('}', _) => self.depth -= 1,
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+}
+
+// The items (functions, types, `use`s...) declared directly within `lines`.
+fn items<'a>(lines: &[&'a str]) -> Vec<&'a str> {
+    let mut items = Vec::new();
+    let mut braces = Braces::default();
+    let mut in_item = false;
+    // Whether the item has a body in braces, rather than ending with a `;`
+    let mut braced = false;
+    for line in lines {
+        if !in_item && braces.depth == 0 && braces.comment == 0 {
+            let trimmed = line.trim();
+            in_item = ITEMS.iter().any(|start| trimmed.starts_with(start));
+            braced = false;
+        }
+        braces.scan(line);
+        if in_item {
+            items.push(*line);
+            braced |= line.contains('{');
+            if braces.depth == 0 && (braced || line.contains(';')) {
+                in_item = false;
+                items.push("");
+            }
+        }
+    }
+    items
+}
+
+// A program that runs just the section called `title` of `source`, a lesson
+// written in a main function.
+pub fn extract(source: &str, title: &str) -> Result<String> {
+    let lines: Vec<&str> = source.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("fn main("))
+        .ok_or("the lesson has no main function")?;
+    let mut braces = Braces::default();
+    braces.scan(lines[start]);
+    let end = (start + 1..lines.len())
+        .find(|&i| {
+            braces.scan(lines[i]);
+            braces.depth == 0
+        })
+        .ok_or("the lesson's main function never ends")?;
+
+    // The sections of main, split the same way `workshop read` splits them
+    let mut sections = vec![("Introduction".to_string(), Vec::new())];
+    for line in &lines[start + 1..end] {
+        match reader::banner(line) {
+            Some(title) => sections.push((title, Vec::new())),
+            None => sections.last_mut().unwrap().1.push(*line),
+        }
+    }
+    if !sections.iter().any(|(t, _)| t == title) {
+        return Err(format!("no section called `{title}` in main").into());
+    }
+
+    let mut program = String::from(
+        "\
+// Parts of the lesson this section doesn't use would only be noise.
+#![allow(unused)]
+",
+    );
+    let mut push = |line: &str| {
+        program.push_str(line);
+        program.push('\n');
+    };
+    lines[..=start].iter().for_each(|line| push(line));
+    for (_, lines) in sections.iter().filter(|(t, _)| t != title) {
+        items(lines).into_iter().for_each(&mut push);
+    }
+    let (_, section) = sections.iter().find(|(t, _)| t == title).unwrap();
+    section.iter().for_each(|line| push(line));
+    lines[end..].iter().for_each(|line| push(line));
+    Ok(program)
+}
+
+// Writes `contents` to `path` unless it's already there, so that cargo doesn't
+// rebuild a section that hasn't changed.
+fn write_if_changed(path: &Path, contents: &str) -> Result<()> {
+    if fs::read_to_string(path).ok().as_deref() != Some(contents) {
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, contents)?;
+    }
+    Ok(())
+}
+
+pub fn command(workspace: &Workspace, args: &[String]) -> Result<ExitCode> {
+    let usage = "usage: workshop run-section LESSON SECTION [--show]";
+    let args = Args::parse(args, &["--show"], &[])?;
+    let show = args.flag("--show");
+    let [lesson, section] = args.positional()[..] else {
+        return Err(usage.into());
+    };
+
+    let lessons = lesson::lessons(workspace)?;
+    let lesson = lesson::find(&lessons, lesson)?;
+    let sections = reader::load(lesson)?;
+    let title = &sections[reader::find_section(&sections, section)?].title;
+    let source = fs::read_to_string(lesson.source())?;
+    let program = extract(&source, title).map_err(|err| format!("{}: {err}", lesson.name))?;
+    if show {
+        print!("{program}");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    // The crate is its own workspace, so that cargo doesn't mistake it for a
+    // missing member of ours.
+    let dir = workspace.root.join("target/sections").join(&lesson.name);
+    write_if_changed(
+        &dir.join("Cargo.toml"),
+        "\
+[package]
+name = \"section\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+[workspace]
+",
+    )?;
+    write_if_changed(&dir.join("src/main.rs"), &program)?;
+    println!(
+        "{} {} section \"{title}\" ({})\n",
+        term::paint("running", Color::Cyan),
+        lesson.name,
+        dir.join("src/main.rs")
+            .strip_prefix(&workspace.root)
+            .unwrap()
+            .display()
+    );
+
+    // Like `workshop lessons`, run it next to the lesson's fixtures. Unlike it,
+    // stdin is yours.
+    let manifest = Manifest::load(workspace)?;
+    let cwd = TempDir::new("section")?;
+    lesson::copy_fixtures(lesson, manifest.lesson_input(&lesson.name), cwd.path())?;
+    let status = Command::new(cargo())
+        .args(["run", "--quiet", "--manifest-path"])
+        .arg(dir.join("Cargo.toml"))
+        .current_dir(cwd.path())
+        .status()
+        .map_err(|err| format!("failed to run cargo: {err}"))?;
+    Ok(if status.success() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_braces() {
+        let mut braces = Braces::default();
+        braces.scan("fn main() { let c = '{'; let s = \"}}\\\"{\"; // }");
+        assert_eq!(braces.depth, 1);
+        braces.scan("fn f<'a>(s: &'a str) -> char { '\\'' } /* { /* } */");
+        assert_eq!((braces.depth, braces.comment), (1, 1));
+        braces.scan("} */ }");
+        assert_eq!((braces.depth, braces.comment), (0, 0));
+    }
+
+    #[test]
+    fn extract_a_section() {
+        let source = "\
+use std::fmt;
+
+fn main() {
+    // ******** One ********
+    let x = 1;
+    #[derive(Debug)]
+    struct Point {
+        x: i32,
+    }
+
+    // ******** Two ********
+    fn double(x: i32) -> i32 {
+        x * 2 // {
+    } // }
+    println!(\"{:?}\", Point { x: double(2) });
+}
+
+fn helper() {}
+";
+        assert_eq!(
+            extract(source, "Two").unwrap(),
+            "\
+// Parts of the lesson this section doesn't use would only be noise.
+#![allow(unused)]
+use std::fmt;
+
+fn main() {
+    #[derive(Debug)]
+    struct Point {
+        x: i32,
+    }
+
+    fn double(x: i32) -> i32 {
+        x * 2 // {
+    } // }
+    println!(\"{:?}\", Point { x: double(2) });
+}
+
+fn helper() {}
+"
+        );
+        // Items end where their braces do, comments or not.
+        let one = extract(source, "One").unwrap();
+        assert!(one.contains("    } // }\n\n    let x = 1;\n"), "{one}");
+        assert!(!one.contains("println!"), "{one}");
+        assert!(extract(source, "Three").is_err());
+    }
+}
@@ -0,0 +1,416 @@
+// `workshop new-exercise NAME --lesson LESSON` sets up everything an exercise
+// needs: the crate under exercises/ (which the workspace picks up through its
+// `exercises/*/` glob), an annotated solution with a starter test, the skeleton
+// generated from it, and an entry at the end of info.toml. It then checks that
+// the new crate builds and that its solution passes the starter test, and if
+// anything fails, removes the crate and the entry again.
+//
+// By default the exercise is a binary graded on its output, like fibonacci.
+// With `--lib` it's a library graded by its tests instead, set up like
+// packages: the skeleton and the solution are swapped by the `solution`
+// feature, so the same tests.rs runs against either.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode, Stdio};
+
+use crate::args::Args;
+use crate::grade::{self, Mode};
+use crate::lesson;
+use crate::manifest::{self, Manifest};
+use crate::skeleton::{self, SOLUTION};
+use crate::term::{self, Color};
+use crate::workspace::Workspace;
+use crate::Result;
+
+const BIN_SOLUTION: &str = "\
+// EXERCISE: Describe what the student has to do here.
+
+fn answer() -> u32 {
+    // @skeleton-begin todo
+    42
+    // @skeleton-end
+}
+
+fn main() {
+    println!(\"The answer is {}\", answer());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_is_42() {
+        assert_eq!(answer(), 42);
+    }
+}
+";
+
+const LIB_SOLUTION: &str = "\
+// EXERCISE: Describe what the student has to do here.
+
+pub fn answer() -> u32 {
+    // @skeleton-begin todo
+    42
+    // @skeleton-end
+}
+
+#[cfg(test)]
+#[path = \"tests.rs\"]
+mod tests;
+";
+
+const LIB_TESTS: &str = "\
+use super::*;
+
+#[test]
+fn answer_is_42() {
+    assert_eq!(answer(), 42);
+}
+";
+
+pub struct Exercise {
+    pub name: String,
+    pub lib: bool,
+    // e.g. "lessons/4-ownership"
+    pub lesson: PathBuf,
+}
+
+impl Exercise {
+    // Rust identifiers can't have dashes, but package names can.
+    fn module(&self) -> String {
+        self.name.replace('-', "_")
+    }
+
+    fn mode(&self) -> Mode {
+        if self.lib {
+            Mode::Test
+        } else {
+            Mode::Output
+        }
+    }
+
+    // The file students edit, relative to the crate
+    fn skeleton(&self) -> PathBuf {
+        if self.lib {
+            PathBuf::from(format!("src/{}.rs", self.module()))
+        } else {
+            PathBuf::from("src/main.rs")
+        }
+    }
+
+    // Every file of the new crate, relative to the crate
+    pub fn files(&self) -> Result<Vec<(PathBuf, String)>> {
+        let name = &self.name;
+        let mut cargo_toml = format!(
+            "\
+[package]
+name = \"{name}\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+"
+        );
+        let solution = if self.lib {
+            cargo_toml.push_str(
+                "\
+[dependencies]
+
+[features]
+# Test the reference solution instead of the exercise
+solution = []
+",
+            );
+            LIB_SOLUTION
+        } else {
+            cargo_toml.push_str(&format!(
+                "\
+[[bin]]
+name = \"{name}-solution\"
+path = \"{SOLUTION}\"
+
+[dependencies]
+"
+            ));
+            BIN_SOLUTION
+        };
+
+        let skeleton = skeleton::generate(solution)?;
+        let mut files = vec![
+            (PathBuf::from("Cargo.toml"), cargo_toml),
+            (PathBuf::from(SOLUTION), solution.to_string()),
+            (self.skeleton(), skeleton),
+        ];
+        if self.lib {
+            let module = self.module();
+            let lib = format!(
+                "\
+// EXERCISE: Fill in {module}.rs until `cargo test` passes.
+
+// Building with `--features solution` swaps in the reference solution, so the
+// very same tests (in tests.rs) can be run against it.
+#[cfg(not(feature = \"solution\"))]
+pub mod {module};
+
+#[cfg(feature = \"solution\")]
+#[path = \"solution.rs\"]
+pub mod {module};
+"
+            );
+            files.push((PathBuf::from("src/lib.rs"), lib));
+            files.push((PathBuf::from("src/tests.rs"), LIB_TESTS.to_string()));
+        }
+        Ok(files)
+    }
+
+    // The text appended to info.toml. The hints are placeholders, but there
+    // have to be some for the manifest to load.
+    pub fn manifest_entry(&self) -> String {
+        let lesson = self.lesson.display();
+        let skeleton = if self.lib {
+            format!("skeleton = \"{}\"\n", self.skeleton().display())
+        } else {
+            String::new()
+        };
+        format!(
+            "
+[[exercises]]
+name = \"{name}\"
+path = \"exercises/{name}\"
+mode = \"{mode}\"
+lesson = \"{lesson}\"
+{skeleton}hints = [
+    \"\"\"
+TODO: a nudge in the right direction.\"\"\",
+    \"\"\"
+TODO: which part of {lesson} covers this.\"\"\",
+    \"\"\"
+TODO: nearly the solution.\"\"\",
+]
+",
+            name = self.name,
+            mode = self.mode(),
+        )
+    }
+}
+
+fn check_name(name: &str) -> Result<()> {
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "`{name}` isn't a good exercise name, use lowercase letters, digits, `-` and `_`"
+        )
+        .into())
+    }
+}
+
+// Writes the exercise's files and its info.toml entry, then checks that it all
+// works.
+fn add(workspace: &Workspace, exercise: &Exercise, path: &Path) -> Result<()> {
+    let dir = workspace.root.join(path);
+    for (file, contents) in exercise.files()? {
+        let file = dir.join(file);
+        fs::create_dir_all(file.parent().unwrap())?;
+        fs::write(&file, contents)?;
+    }
+    println!("{} {}", term::paint("created", Color::Cyan), path.display());
+
+    let mut info = OpenOptions::new()
+        .append(true)
+        .open(workspace.root.join(manifest::FILE))?;
+    info.write_all(exercise.manifest_entry().as_bytes())?;
+    println!(
+        "{} {} (fill in its hints!)",
+        term::paint("added", Color::Cyan),
+        manifest::FILE
+    );
+
+    // Reloading checks that the workspace picked up the crate, and that the
+    // manifest entry agrees with it.
+    let workspace = Workspace::load(&workspace.root)?;
+    if !workspace.members.iter().any(|member| member == path) {
+        return Err(format!(
+            "{} isn't a workspace member, add it to `members` in Cargo.toml",
+            path.display()
+        )
+        .into());
+    }
+    Manifest::load(&workspace)?;
+
+    let name = &exercise.name;
+    let solution_bin = format!("{name}-solution");
+    let mut solution_tests = vec!["test", "--quiet", "-p", name];
+    if exercise.lib {
+        solution_tests.extend(["--features", "solution"]);
+    } else {
+        solution_tests.extend(["--bin", &solution_bin]);
+    }
+    for args in [
+        vec!["build", "--quiet", "-p", name, "--all-targets"],
+        solution_tests,
+    ] {
+        println!(
+            "{} cargo {}",
+            term::paint("running", Color::Cyan),
+            args.join(" ")
+        );
+        let status = Command::new(grade::cargo())
+            .args(&args)
+            .current_dir(&workspace.root)
+            .stdin(Stdio::null())
+            .status()
+            .map_err(|err| format!("failed to run cargo: {err}"))?;
+        if !status.success() {
+            return Err(format!("`cargo {}` failed", args.join(" ")).into());
+        }
+    }
+
+    Ok(())
+}
+
+// Adds the exercise, or if anything goes wrong, undoes it all, so that running
+// the command again (once the problem is fixed) starts from scratch.
+fn create(workspace: &Workspace, exercise: &Exercise, path: &Path) -> Result<()> {
+    let info_path = workspace.root.join(manifest::FILE);
+    let info = fs::read_to_string(&info_path)?;
+    let Err(err) = add(workspace, exercise, path) else {
+        return Ok(());
+    };
+    let dir = workspace.root.join(path);
+    if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+    fs::write(&info_path, info)?;
+    Err(format!(
+        "{err}\nremoved {} and its {} entry again",
+        path.display(),
+        manifest::FILE
+    )
+    .into())
+}
+
+pub fn command(workspace: &Workspace, args: &[String]) -> Result<ExitCode> {
+    let usage = "usage: workshop new-exercise NAME --lesson LESSON [--lib]";
+    let args = Args::parse(args, &["--lib"], &[("--lesson", "a lesson")])?;
+    let lib = args.flag("--lib");
+    let (&[name], Some(lesson)) = (&args.positional()[..], args.value("--lesson")) else {
+        return Err(usage.into());
+    };
+    let name = name.to_string();
+
+    check_name(&name)?;
+    let manifest = Manifest::load(workspace)?;
+    if manifest.exercises.iter().any(|e| e.name == name) {
+        return Err(format!("there already is an exercise called `{name}`").into());
+    }
+    let path = Path::new("exercises").join(&name);
+    let dir = workspace.root.join(&path);
+    if dir.exists() {
+        return Err(format!("{} already exists", path.display()).into());
+    }
+    let lessons = lesson::lessons(workspace)?;
+    let exercise = Exercise {
+        name,
+        lib,
+        lesson: lesson::find(&lessons, lesson)?.path.clone(),
+    };
+
+    create(workspace, &exercise, &path)?;
+
+    let name = &exercise.name;
+    println!(
+        "\nDone! Now write the solution in {}, then run `workshop generate {name}`.",
+        path.join(SOLUTION).display()
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp::TempDir;
+    use crate::toml;
+
+    #[test]
+    fn exercise_names() {
+        assert!(check_name("fizz-buzz_2").is_ok());
+        assert!(check_name("FizzBuzz").is_err());
+        assert!(check_name("2fizz").is_err());
+        assert!(check_name("../fizz").is_err());
+    }
+
+    #[test]
+    fn scaffold_files() {
+        for lib in [false, true] {
+            let exercise = Exercise {
+                name: "fizz-buzz".to_string(),
+                lib,
+                lesson: PathBuf::from("lessons/3-nuts-and-bolts"),
+            };
+            let files = exercise.files().unwrap();
+            let file = |path: &str| {
+                files
+                    .iter()
+                    .find(|(p, _)| p == Path::new(path))
+                    .map(|(_, contents)| contents.as_str())
+            };
+
+            let cargo_toml = toml::parse(file("Cargo.toml").unwrap()).unwrap();
+            assert_eq!(cargo_toml.contains_key("bin"), !lib);
+            assert_eq!(cargo_toml.contains_key("features"), lib);
+
+            let skeleton = file(if lib {
+                "src/fizz_buzz.rs"
+            } else {
+                "src/main.rs"
+            })
+            .unwrap();
+            assert!(skeleton.contains("    todo!()\n"));
+            assert!(!skeleton.contains("@skeleton"));
+            assert_eq!(file("src/lib.rs").is_some(), lib);
+
+            let entry = toml::parse(&exercise.manifest_entry()).unwrap();
+            let entry = entry["exercises"].as_array().unwrap()[0]
+                .as_table()
+                .unwrap();
+            assert_eq!(
+                entry["mode"].as_str(),
+                Some(if lib { "test" } else { "output" })
+            );
+            assert_eq!(entry["hints"].as_array().unwrap().len(), 3);
+        }
+    }
+
+    #[test]
+    fn undo_a_failed_exercise() {
+        // A workspace that doesn't pick up anything under exercises/
+        let root = TempDir::new("new-exercise").unwrap();
+        let root = root.path();
+        fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = []\n").unwrap();
+        let info = "[[exercises]]\nname = \"first\"\n";
+        fs::write(root.join(manifest::FILE), info).unwrap();
+
+        let workspace = Workspace::load(root).unwrap();
+        let exercise = Exercise {
+            name: "demo".to_string(),
+            lib: false,
+            lesson: PathBuf::from("lessons/1-intro"),
+        };
+        let path = Path::new("exercises/demo");
+        let err = create(&workspace, &exercise, path).unwrap_err().to_string();
+        assert!(
+            err.starts_with("exercises/demo isn't a workspace member"),
+            "{err}"
+        );
+        assert!(!root.join(path).exists());
+        assert_eq!(fs::read_to_string(root.join(manifest::FILE)).unwrap(), info);
+    }
+}
@@ -0,0 +1,172 @@
+// `workshop self-check` is for maintainers: it checks that everything we ship
+// still works, without tripping over the parts that are broken on purpose.
+// `cargo build --workspace` can't do that, since the student skeletons don't
+// compile (packages has `-> ???`) or panic until they're solved.
+//
+// So each piece is checked on its own. Every lesson has to build, run and print
+// its golden output, like with `workshop lessons`. Every reference solution has
+// to pass: the `<name>-solution` binary's tests and a run of it (and the tests
+// of the crate's library, if it has one), or for test exercises the crate's
+// tests with `--features solution`. The skeletons are graded too, but failing
+// is what they're expected to do, so only the lessons and solutions count as
+// regressions.
+
+use std::process::ExitCode;
+use std::time::Duration;
+
+use crate::grade::{self, Mode, Outcome};
+use crate::lesson;
+use crate::manifest::{Exercise, Manifest};
+use crate::term::{self, Color};
+use crate::workspace::{Crate, Workspace};
+use crate::Result;
+
+// What went wrong with a solution, if anything.
+pub fn check_solution(workspace: &Workspace, exercise: &Exercise) -> Result<Option<String>> {
+    let name = &exercise.name;
+    let solution_bin = format!("{name}-solution");
+    let runs = match exercise.mode {
+        Mode::Test => vec![vec![
+            "test",
+            "--quiet",
+            "-p",
+            name,
+            "--features",
+            "solution",
+        ]],
+        Mode::Assert | Mode::Output => {
+            if !Crate::load(&exercise.dir)?.bins.contains(&solution_bin) {
+                return Ok(Some(format!("there's no `{solution_bin}` binary")));
+            }
+            let mut runs = vec![
+                vec!["test", "--quiet", "-p", name, "--bin", &solution_bin],
+                vec!["run", "--quiet", "-p", name, "--bin", &solution_bin],
+            ];
+            // Extra reference code can live in a library next to the exercise,
+            // like fibonacci's.
+            if exercise.dir.join("src/lib.rs").is_file() {
+                runs.push(vec!["test", "--quiet", "-p", name, "--lib"]);
+            }
+            runs
+        }
+    };
+
+    for args in runs {
+        let args: Vec<String> = args.into_iter().map(String::from).collect();
+        let output = grade::run_cargo(workspace, &args)?;
+        if output.status.success() {
+            continue;
+        }
+        let outcome = Outcome {
+            passed: false,
+            output: format!("{}{}", output.stdout, output.stderr),
+            mismatch: None,
+            elapsed: Duration::ZERO,
+        };
+        let problem = outcome
+            .first_problem(term::use_color())
+            .unwrap_or_else(|| outcome.output.trim_end().to_string());
+        return Ok(Some(format!(
+            "`cargo {}` failed\n{problem}",
+            args.join(" ")
+        )));
+    }
+    Ok(None)
+}
+
+pub fn command(workspace: &Workspace, args: &[String]) -> Result<ExitCode> {
+    if let Some(arg) = args.first() {
+        return Err(format!("unexpected argument `{arg}`").into());
+    }
+
+    let manifest = Manifest::load(workspace)?;
+    let lessons = lesson::lessons(workspace)?;
+    println!(
+        "Checking {} lessons and {} exercises...",
+        lessons.len(),
+        manifest.exercises.len()
+    );
+
+    let mut rows = Vec::new();
+    let mut regressions = Vec::new();
+    let mut row = |what: String, checked: &str, problem: Option<String>| {
+        let result = match &problem {
+            None => ("PASS".to_string(), Some(Color::Green)),
+            Some(_) => ("FAIL".to_string(), Some(Color::Red)),
+        };
+        rows.push(vec![
+            (what.clone(), None),
+            (checked.to_string(), None),
+            result,
+        ]);
+        if let Some(problem) = problem {
+            regressions.push((what, problem));
+        }
+    };
+
+    for lesson in &lessons {
+        let run = lesson::run(workspace, lesson, manifest.lesson_input(&lesson.name))?;
+        row(
+            lesson.name.clone(),
+            "builds and prints its expected output",
+            lesson::problem(lesson, &run)?,
+        );
+    }
+
+    let mut skeletons = Vec::new();
+    for exercise in &manifest.exercises {
+        row(
+            exercise.name.clone(),
+            "solution passes",
+            check_solution(workspace, exercise)?,
+        );
+        if grade::grade(workspace, exercise)?.passed {
+            skeletons.push(exercise.name.as_str());
+        }
+    }
+    let skeleton_count = manifest.exercises.len() - skeletons.len();
+
+    println!();
+    term::print_table(&["Checked", "", "Result"], &rows);
+    println!(
+        "\n{skeleton_count}/{} skeletons fail to grade, as they should until they're solved.",
+        manifest.exercises.len()
+    );
+    for name in skeletons {
+        println!(
+            "{} {name} already passes, has its skeleton been solved? (`workshop generate --check {name}` tells)",
+            term::paint("note:", Color::Cyan)
+        );
+    }
+
+    for (what, problem) in &regressions {
+        println!("\n{}: {problem}", term::paint(what, Color::Red));
+    }
+    if regressions.is_empty() {
+        println!("\nAll lessons and solutions work.");
+        Ok(ExitCode::SUCCESS)
+    } else {
+        let n = regressions.len();
+        println!("\n{n} regression{}", if n == 1 { "" } else { "s" });
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solutions_pass() {
+        let workspace = Workspace::ours();
+        let manifest = Manifest::load(&workspace).unwrap();
+        for exercise in &manifest.exercises {
+            assert_eq!(
+                check_solution(&workspace, exercise).unwrap(),
+                None,
+                "{}",
+                exercise.name
+            );
+        }
+    }
+}
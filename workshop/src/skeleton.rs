@@ -0,0 +1,189 @@
+// Every exercise comes in two copies: the solution, and the skeleton the
+// students start from. Rather than keep the two in sync by hand, the skeleton
+// is generated from the solution, which marks the parts to cut out:
+//
+//     fn main() {
+//         // @skeleton-begin
+//         // @skeleton: // EXERCISE! Print out the first 10 fibonacci numbers
+//         for i in 0..10 {
+//             println!("{}", fibonacci(i));
+//         }
+//         // @skeleton-end
+//     }
+//
+// Everything between `@skeleton-begin` and `@skeleton-end` is left out of the
+// skeleton, except for `// @skeleton: ...` lines, which are put in its place
+// (keeping their indentation). Writing `@skeleton-begin todo` additionally
+// leaves a `todo!()` behind, so that the skeleton still compiles.
+
+use std::fs;
+use std::process::ExitCode;
+
+use crate::args::Args;
+use crate::diff;
+use crate::manifest::Manifest;
+use crate::term::{self, Color};
+use crate::workspace::Workspace;
+use crate::{grade, Result};
+
+const BEGIN: &str = "// @skeleton-begin";
+const END: &str = "// @skeleton-end";
+const KEEP: &str = "// @skeleton:";
+
+// Where each exercise keeps its solution, relative to the exercise crate
+pub const SOLUTION: &str = "src/solution.rs";
+
+pub fn generate(solution: &str) -> std::result::Result<String, String> {
+    let mut skeleton = String::new();
+    // The line number and `todo!()` indentation of the region we're in, if any
+    let mut region: Option<(usize, Option<String>)> = None;
+
+    for (i, line) in solution.split_inclusive('\n').enumerate() {
+        let number = i + 1;
+        let trimmed = line.trim();
+        let indent = &line[..line.len() - line.trim_start().len()];
+
+        if let Some(rest) = trimmed.strip_prefix(BEGIN) {
+            if let Some((start, _)) = region {
+                return Err(format!(
+                    "line {number}: region opened on line {start} is still open"
+                ));
+            }
+            let todo = match rest.trim() {
+                "" => None,
+                "todo" => Some(indent.to_string()),
+                other => return Err(format!("line {number}: unknown region kind `{other}`")),
+            };
+            region = Some((number, todo));
+        } else if trimmed == END {
+            let Some((_, todo)) = region.take() else {
+                return Err(format!(
+                    "line {number}: `{END}` without a matching `{BEGIN}`"
+                ));
+            };
+            if let Some(indent) = todo {
+                skeleton.push_str(&format!("{indent}todo!()\n"));
+            }
+        } else if let Some(text) = trimmed.strip_prefix(KEEP) {
+            if region.is_none() {
+                return Err(format!("line {number}: `{KEEP}` outside of a region"));
+            }
+            let text = text.strip_prefix(' ').unwrap_or(text);
+            if text.is_empty() {
+                skeleton.push('\n');
+            } else {
+                skeleton.push_str(&format!("{indent}{text}\n"));
+            }
+        } else if region.is_none() {
+            skeleton.push_str(line);
+        }
+    }
+
+    match region {
+        Some((start, _)) => Err(format!("region opened on line {start} is never closed")),
+        None => Ok(skeleton),
+    }
+}
+
+pub fn command(workspace: &Workspace, args: &[String]) -> Result<ExitCode> {
+    let args = Args::parse(args, &["--check"], &[])?;
+    let check = args.flag("--check");
+    let names: Vec<String> = args.positional().into_iter().map(String::from).collect();
+
+    let mut stale = 0;
+    for exercise in grade::select(Manifest::load(workspace)?, &names)? {
+        let solution_path = exercise.dir.join(SOLUTION);
+        let skeleton_path = exercise.dir.join(&exercise.skeleton);
+        let solution = fs::read_to_string(&solution_path)
+            .map_err(|err| format!("failed to read {}: {err}", solution_path.display()))?;
+        let skeleton = generate(&solution)
+            .map_err(|err| format!("{}/{SOLUTION}: {err}", exercise.path.display()))?;
+
+        let current = fs::read_to_string(&skeleton_path).unwrap_or_default();
+        let shown = exercise.path.join(&exercise.skeleton);
+        if current == skeleton {
+            println!(
+                "{} {}",
+                term::paint("up to date", Color::Green),
+                shown.display()
+            );
+        } else if check {
+            stale += 1;
+            println!(
+                "{} {}",
+                term::paint("out of date", Color::Red),
+                shown.display()
+            );
+            let diff = diff::lines(&current, &skeleton);
+            let names = ("committed", "generated");
+            println!("{}\n", diff::unified(&diff, 3, names, term::use_color()));
+        } else {
+            fs::write(&skeleton_path, skeleton)?;
+            println!(
+                "{} {}",
+                term::paint("generated", Color::Cyan),
+                shown.display()
+            );
+        }
+    }
+
+    if stale == 0 {
+        Ok(ExitCode::SUCCESS)
+    } else {
+        println!("{stale} skeleton(s) are out of date, run `workshop generate` to update them");
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keep_lines_replace_the_region() {
+        let solution = "\
+enum Message {
+    // @skeleton-begin
+    // @skeleton: // Exercise! implement the variants
+    Move { x: i32, y: i32 },
+    Quit,
+    // @skeleton-end
+}
+";
+        assert_eq!(
+            generate(solution).unwrap(),
+            "enum Message {\n    // Exercise! implement the variants\n}\n"
+        );
+    }
+
+    #[test]
+    fn todo_regions_still_compile() {
+        let solution = "\
+fn fibonacci(n: u32) -> u32 {
+    // @skeleton-begin todo
+    if n < 2 { n } else { fibonacci(n - 1) + fibonacci(n - 2) }
+    // @skeleton-end
+}
+";
+        assert_eq!(
+            generate(solution).unwrap(),
+            "fn fibonacci(n: u32) -> u32 {\n    todo!()\n}\n"
+        );
+    }
+
+    #[test]
+    fn unbalanced_markers() {
+        assert_eq!(
+            generate("// @skeleton-begin\n// @skeleton-begin\n").unwrap_err(),
+            "line 2: region opened on line 1 is still open"
+        );
+        assert_eq!(
+            generate("// @skeleton-end\n").unwrap_err(),
+            "line 1: `// @skeleton-end` without a matching `// @skeleton-begin`"
+        );
+        assert_eq!(
+            generate("fn main() {\n// @skeleton-begin\n}\n").unwrap_err(),
+            "region opened on line 2 is never closed"
+        );
+    }
+}
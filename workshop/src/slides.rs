@@ -0,0 +1,232 @@
+// `workshop slides` turns the lessons into slide decks, one HTML file per
+// lesson that needs nothing but a browser, so the workshop can be presented
+// straight from the repository. There's a title slide listing the sections,
+// then a slide per section (as `workshop read` splits them) with the comments
+// as text and the code as highlighted code. Arrow keys, space and page up/down
+// move between slides, and printing gives a page per slide.
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use crate::args::Args;
+use crate::lesson::{self, Lesson};
+use crate::reader::{self, Block, Section};
+use crate::report::escape_xml;
+use crate::term::{self, Color};
+use crate::workspace::Workspace;
+use crate::Result;
+
+const STYLE: &str = "\
+<style>
+  body { margin: 0; font-family: system-ui, sans-serif; color: #222; background: #fafafa; }
+  .slide { display: none; box-sizing: border-box; height: 100vh; overflow: auto; padding: 4vh 6vw; }
+  .slide.current { display: block; }
+  .title { display: none; flex-direction: column; justify-content: center; }
+  .title.current { display: flex; }
+  h1 { font-size: 3em; margin: 0 0 0.2em; }
+  h2 { font-size: 2em; margin: 0 0 0.8em; border-bottom: 2px solid #dea584; }
+  p { font-size: 1.3em; line-height: 1.4; white-space: pre-line; max-width: 50em; }
+  pre { font-size: 1.1em; background: #272822; color: #f8f8f2; padding: 0.8em 1.2em; border-radius: 6px; overflow-x: auto; }
+  .kw { color: #66d9ef; }
+  .str { color: #a6e22e; }
+  .comment { color: #999; }
+  .lesson { color: #888; font-size: 1.2em; }
+  .counter { position: fixed; right: 1em; bottom: 0.6em; color: #999; }
+  @media print {
+    .slide, .title { display: block !important; height: auto; page-break-after: always; }
+    .counter { display: none; }
+  }
+</style>
+";
+
+// Moves between slides, keeping the slide number in the URL so that reloading
+// stays put.
+const SCRIPT: &str = "\
+<script>
+  const slides = document.querySelectorAll('.slide');
+  const counter = document.querySelector('.counter');
+  let current = 0;
+  function show(n) {
+    current = Math.max(0, Math.min(slides.length - 1, n));
+    slides.forEach((slide, i) => slide.classList.toggle('current', i === current));
+    counter.textContent = (current + 1) + ' / ' + slides.length;
+    history.replaceState(null, '', '#' + (current + 1));
+  }
+  document.addEventListener('keydown', event => {
+    if (['ArrowRight', 'ArrowDown', 'PageDown', ' '].includes(event.key)) show(current + 1);
+    else if (['ArrowLeft', 'ArrowUp', 'PageUp'].includes(event.key)) show(current - 1);
+    else if (event.key === 'Home') show(0);
+    else if (event.key === 'End') show(slides.length - 1);
+    else return;
+    event.preventDefault();
+  });
+  show(parseInt(location.hash.slice(1) || '1') - 1);
+</script>
+";
+
+// "3-nuts-and-bolts" is "Nuts and bolts"
+fn title(lesson: &Lesson) -> String {
+    let words = lesson
+        .name
+        .split_once('-')
+        .map_or(lesson.name.as_str(), |(_, words)| words)
+        .replace('-', " ");
+    let mut chars = words.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+fn code(lines: &[String]) -> String {
+    let mut out = String::from("<pre><code>");
+    for line in lines {
+        for (text, color) in reader::tokens(line) {
+            let text = escape_xml(&text);
+            match color {
+                Some(Color::Cyan) => out.push_str(&format!("<span class=\"kw\">{text}</span>")),
+                Some(Color::Green) => out.push_str(&format!("<span class=\"str\">{text}</span>")),
+                Some(Color::Dim) => out.push_str(&format!("<span class=\"comment\">{text}</span>")),
+                _ => out.push_str(&text),
+            }
+        }
+        out.push('\n');
+    }
+    out.push_str("</code></pre>\n");
+    out
+}
+
+pub fn deck(lesson: &Lesson, sections: &[Section]) -> String {
+    let title = escape_xml(&title(lesson));
+    let mut out = format!(
+        "\
+<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>{title}</title>
+{STYLE}</head>
+<body>
+<section class=\"slide title\">
+<div class=\"lesson\">Lesson {}</div>
+<h1>{title}</h1>
+<ol>
+",
+        lesson.number
+    );
+    for section in sections {
+        out.push_str(&format!("<li>{}</li>\n", escape_xml(&section.title)));
+    }
+    out.push_str("</ol>\n</section>\n");
+
+    for section in sections {
+        out.push_str(&format!(
+            "<section class=\"slide\">\n<h2>{}</h2>\n",
+            escape_xml(&section.title)
+        ));
+        for block in section.blocks() {
+            match block {
+                Block::Prose(lines) => {
+                    out.push_str(&format!("<p>{}</p>\n", escape_xml(&lines.join("\n"))))
+                }
+                Block::Code(lines) => out.push_str(&code(&lines)),
+            }
+        }
+        out.push_str("</section>\n");
+    }
+    out.push_str("<div class=\"counter\"></div>\n");
+    out.push_str(SCRIPT);
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+// A page linking to every deck
+fn index(lessons: &[&Lesson]) -> String {
+    let mut out = String::from(
+        "\
+<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>Rust workshop</title>
+<style>
+  body { font-family: system-ui, sans-serif; margin: 2em; color: #222; }
+  li { font-size: 1.3em; margin: 0.3em 0; }
+</style>
+</head>
+<body>
+<h1>Rust workshop</h1>
+<ol>
+",
+    );
+    for lesson in lessons {
+        out.push_str(&format!(
+            "<li value=\"{}\"><a href=\"{}.html\">{}</a></li>\n",
+            lesson.number,
+            escape_xml(&lesson.name),
+            escape_xml(&title(lesson))
+        ));
+    }
+    out.push_str("</ol>\n</body>\n</html>\n");
+    out
+}
+
+pub fn command(workspace: &Workspace, args: &[String]) -> Result<ExitCode> {
+    let args = Args::parse(args, &[], &[("--out", "a directory")])?;
+    let out = match args.value("--out") {
+        Some(dir) => PathBuf::from(dir),
+        None => workspace.root.join("target/slides"),
+    };
+    let selected = lesson::select(workspace, &args.positional())?;
+
+    fs::create_dir_all(&out).map_err(|err| format!("failed to create {}: {err}", out.display()))?;
+    for lesson in &selected {
+        let sections = reader::load(lesson)?;
+        let path = out.join(format!("{}.html", lesson.name));
+        fs::write(&path, deck(lesson, &sections))
+            .map_err(|err| format!("failed to write {}: {err}", path.display()))?;
+        println!(
+            "{} {} ({} slides)",
+            term::paint("wrote", Color::Cyan),
+            path.display(),
+            sections.len() + 1
+        );
+    }
+    // The index links every deck in the directory, including ones written by
+    // earlier runs for other lessons.
+    let lessons = lesson::lessons(workspace)?;
+    let decks: Vec<&Lesson> = lessons
+        .iter()
+        .filter(|lesson| out.join(format!("{}.html", lesson.name)).is_file())
+        .collect();
+    let path = out.join("index.html");
+    fs::write(&path, index(&decks))
+        .map_err(|err| format!("failed to write {}: {err}", path.display()))?;
+    println!("{} {}", term::paint("wrote", Color::Cyan), path.display());
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ownership_deck() {
+        let workspace = Workspace::ours();
+        let lessons = lesson::lessons(&workspace).unwrap();
+        let lesson = lesson::find(&lessons, "4").unwrap();
+        assert_eq!(title(lesson), "Ownership");
+        assert_eq!(
+            title(lesson::find(&lessons, "3").unwrap()),
+            "Nuts and bolts"
+        );
+
+        let deck = deck(lesson, &reader::load(lesson).unwrap());
+        // A title slide and one for each of the five sections
+        assert_eq!(deck.matches("<section class=\"slide").count(), 6);
+        assert!(deck.contains("<h2>The &quot;str&quot; Type</h2>"));
+        assert!(deck.contains("<span class=\"kw\">let</span> my_str"));
+        assert!(!deck.contains("// ****"));
+    }
+}
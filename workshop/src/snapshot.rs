@@ -0,0 +1,113 @@
+// Golden files: what a lesson printed the last time someone checked it was
+// right. Comparing against them is exact, except that `[..]` in a golden line
+// matches any text, for the odd line that's different on every run (like the
+// dangling reference in lesson 4).
+
+use crate::diff;
+
+pub const WILDCARD: &str = "[..]";
+
+pub fn matches(golden: &str, actual: &str) -> bool {
+    let golden: Vec<&str> = golden.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    golden.len() == actual.len()
+        && golden
+            .iter()
+            .zip(&actual)
+            .all(|(golden, actual)| line_matches(golden, actual))
+}
+
+fn line_matches(golden: &str, actual: &str) -> bool {
+    let mut pieces = golden.split(WILDCARD);
+    let first = pieces.next().unwrap_or_default();
+    let Some(mut rest) = actual.strip_prefix(first) else {
+        return false;
+    };
+    let pieces: Vec<&str> = pieces.collect();
+    let Some((last, middle)) = pieces.split_last() else {
+        // No wildcard at all
+        return rest.is_empty();
+    };
+    for piece in middle {
+        match rest.find(piece) {
+            Some(i) => rest = &rest[i + piece.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+// The new golden file for `actual`. Lines that still match keep their
+// wildcards, so blessing new output doesn't lose them.
+pub fn bless(golden: &str, actual: &str) -> String {
+    let golden: Vec<&str> = golden.lines().collect();
+    let mut out = String::new();
+    for (i, line) in actual.lines().enumerate() {
+        match golden.get(i) {
+            Some(old) if old.contains(WILDCARD) && line_matches(old, line) => out.push_str(old),
+            _ => out.push_str(line),
+        }
+        out.push('\n');
+    }
+    out
+}
+
+// A diff of the golden file against `actual`, in which lines matched by a
+// wildcard don't show up as changes.
+pub fn describe(golden: &str, actual: &str, names: (&str, &str), color: bool) -> String {
+    let expected: String = actual
+        .lines()
+        .zip(golden.lines())
+        .map(|(actual, golden)| {
+            if golden.contains(WILDCARD) && line_matches(golden, actual) {
+                actual
+            } else {
+                golden
+            }
+        })
+        .chain(golden.lines().skip(actual.lines().count()))
+        .map(|line| format!("{line}\n"))
+        .collect();
+    let diff = diff::lines(&expected, actual);
+    diff::unified(&diff, 3, names, color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards() {
+        assert!(line_matches(
+            "After reallocation, 1 == [..]",
+            "After reallocation, 1 == 42"
+        ));
+        assert!(line_matches("[..] == [..]", "1 == 2"));
+        assert!(line_matches("a[..]b[..]c", "aXbYc"));
+        assert!(line_matches("exact", "exact"));
+        assert!(!line_matches("exact", "exactly"));
+        assert!(!line_matches("ab[..]ba", "aba"));
+        assert!(!line_matches("a[..]b[..]c", "acb"));
+
+        assert!(matches("x = [..]\ny\n", "x = 1\ny\n"));
+        assert!(!matches("x = [..]\n", "x = 1\ny\n"));
+    }
+
+    #[test]
+    fn bless_keeps_wildcards() {
+        let golden = "Before, 1 == 1\nAfter, 1 == [..]\n";
+        assert_eq!(bless(golden, "Before, 1 == 1\nAfter, 1 == 7\n"), golden);
+        assert_eq!(
+            bless(golden, "Before, 2 == 2\nAfter, 2 == 7\ndone\n"),
+            "Before, 2 == 2\nAfter, 2 == 7\ndone\n"
+        );
+    }
+
+    #[test]
+    fn describe_ignores_wildcard_lines() {
+        let golden = "a\nb = [..]\nc\n";
+        let description = describe(golden, "a\nb = 9\nC\n", ("golden", "actual"), false);
+        assert!(description.contains("-c\n+C"), "{description}");
+        assert!(!description.contains("-b"), "{description}");
+    }
+}
@@ -0,0 +1,202 @@
+// Where you are in the workshop: every lesson in order, each followed by the
+// exercises that build on it. A lesson unlocks once the one before it has been
+// read, and an exercise unlocks once its lesson has been read.
+
+use std::fmt;
+use std::process::ExitCode;
+
+use crate::lesson::{self, Lesson};
+use crate::manifest::{Exercise, Manifest};
+use crate::progress::Progress;
+use crate::quiz;
+use crate::term::{self, Color};
+use crate::workspace::Workspace;
+use crate::Result;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum State {
+    Completed,
+    InProgress,
+    Locked,
+}
+
+impl State {
+    fn color(self) -> Color {
+        match self {
+            State::Completed => Color::Green,
+            State::InProgress => Color::Cyan,
+            State::Locked => Color::Dim,
+        }
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            State::Completed => "completed",
+            State::InProgress => "in progress",
+            State::Locked => "locked",
+        })
+    }
+}
+
+pub fn lesson_states(lessons: &[Lesson], progress: &Progress) -> Vec<State> {
+    let mut previous_read = true;
+    lessons
+        .iter()
+        .map(|lesson| {
+            let read = progress.has_read(&lesson.name);
+            let state = if read {
+                State::Completed
+            } else if previous_read {
+                State::InProgress
+            } else {
+                State::Locked
+            };
+            previous_read = read;
+            state
+        })
+        .collect()
+}
+
+pub fn exercise_state(exercise: &Exercise, lesson: &Lesson, progress: &Progress) -> State {
+    if progress.passed(&exercise.name) == Some(true) {
+        State::Completed
+    } else if !progress.has_read(&lesson.name) {
+        State::Locked
+    } else {
+        State::InProgress
+    }
+}
+
+pub fn command(workspace: &Workspace, args: &[String]) -> Result<ExitCode> {
+    if let Some(arg) = args.first() {
+        return Err(format!("unexpected argument `{arg}`").into());
+    }
+
+    let lessons = lesson::lessons(workspace)?;
+    let manifest = Manifest::load(workspace)?;
+    let progress = Progress::load(workspace)?;
+
+    let mut rows = Vec::new();
+    let mut next = None;
+    for (lesson, state) in lessons.iter().zip(lesson_states(&lessons, &progress)) {
+        if state == State::InProgress && next.is_none() {
+            next = Some(format!(
+                "read {}, then run `workshop mark-read {}`",
+                lesson.path.display(),
+                lesson.number
+            ));
+        }
+        let quiz = match progress.quizzes.get(&lesson.name) {
+            Some((right, total)) => format!("quiz: {right}/{total}"),
+            None if lesson.dir.join(quiz::FILE).is_file() => "quiz: not taken yet".to_string(),
+            None => String::new(),
+        };
+        rows.push(vec![
+            (lesson.name.clone(), None),
+            (state.to_string(), Some(state.color())),
+            (quiz, Some(Color::Dim)),
+        ]);
+
+        for exercise in manifest
+            .exercises
+            .iter()
+            .filter(|e| e.lesson == lesson.path)
+        {
+            let state = exercise_state(exercise, lesson, &progress);
+            if state == State::InProgress && next.is_none() {
+                next = Some(format!("run `workshop grade {}`", exercise.name));
+            }
+            let last = match progress.passed(&exercise.name) {
+                Some(true) => "passed",
+                Some(false) => "failed",
+                None => "not graded yet",
+            };
+            rows.push(vec![
+                (format!("  └ {}", exercise.name), None),
+                (state.to_string(), Some(state.color())),
+                (
+                    format!(
+                        "{last}, {}/{} hints",
+                        progress.hints_used(&exercise.name),
+                        exercise.hints.len()
+                    ),
+                    Some(Color::Dim),
+                ),
+            ]);
+        }
+    }
+
+    term::print_table(&["Lesson", "Status", ""], &rows);
+    let read = lessons
+        .iter()
+        .filter(|l| progress.has_read(&l.name))
+        .count();
+    let passed = manifest
+        .exercises
+        .iter()
+        .filter(|e| progress.passed(&e.name) == Some(true))
+        .count();
+    println!(
+        "\n{read}/{} lessons read, {passed}/{} exercises passed",
+        lessons.len(),
+        manifest.exercises.len()
+    );
+    match next {
+        Some(next) => println!("Next up: {next}."),
+        None => println!("All done, nice work!"),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lessons_unlock_in_order() {
+        let workspace = Workspace::ours();
+        let lessons = lesson::lessons(&workspace).unwrap();
+        let manifest = Manifest::load(&workspace).unwrap();
+        let find = |name: &str| lesson::find(&lessons, name).unwrap();
+
+        let mut progress = Progress::default();
+        progress.lessons.insert("1-hello-world".to_string());
+        progress.lessons.insert("3-nuts-and-bolts".to_string());
+        progress.exercises.insert("fibonacci".to_string(), true);
+        progress.exercises.insert("messages".to_string(), false);
+
+        let states = lesson_states(&lessons, &progress);
+        assert_eq!(
+            states[..5],
+            [
+                State::Completed,
+                State::InProgress,
+                State::Completed,
+                State::InProgress,
+                State::Locked
+            ]
+        );
+
+        let exercise = |name: &str| manifest.exercises.iter().find(|e| e.name == name).unwrap();
+        assert_eq!(
+            exercise_state(exercise("fibonacci"), find("3"), &progress),
+            State::Completed
+        );
+        assert_eq!(
+            exercise_state(exercise("packages"), find("6"), &progress),
+            State::Locked
+        );
+        assert_eq!(
+            exercise_state(exercise("messages"), find("7"), &progress),
+            State::Locked
+        );
+        // Passing counts even if the lesson was skipped.
+        progress.exercises.insert("messages".to_string(), true);
+        assert_eq!(
+            exercise_state(exercise("messages"), find("7"), &progress),
+            State::Completed
+        );
+    }
+}
@@ -0,0 +1,76 @@
+// Terminal helpers. We only ever need a handful of colors, so rather than pull
+// in a crate we write the ANSI escape codes ourselves.
+
+use std::env;
+use std::io::{self, IsTerminal};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Color {
+    Red,
+    Green,
+    Cyan,
+    Bold,
+    Dim,
+}
+
+impl Color {
+    fn code(self) -> &'static str {
+        match self {
+            Color::Red => "31",
+            Color::Green => "32",
+            Color::Cyan => "36",
+            Color::Bold => "1",
+            Color::Dim => "2",
+        }
+    }
+}
+
+// Colors are only used when stdout is a terminal and NO_COLOR isn't set.
+pub fn use_color() -> bool {
+    env::var_os("NO_COLOR").is_none() && io::stdout().is_terminal()
+}
+
+pub fn paint(text: &str, color: Color) -> String {
+    paint_with(text, color, use_color())
+}
+
+pub fn paint_with(text: &str, color: Color, enabled: bool) -> String {
+    if enabled {
+        format!("\x1b[{}m{text}\x1b[0m", color.code())
+    } else {
+        text.to_string()
+    }
+}
+
+// Prints `rows` as a table with left-aligned columns. The padding is computed
+// on the plain text so that colors don't throw the alignment off.
+pub fn print_table(header: &[&str], rows: &[Vec<(String, Option<Color>)>]) {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (width, (cell, _)) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let header: Vec<String> = header
+        .iter()
+        .zip(&widths)
+        .map(|(h, &w)| paint(&format!("{h:w$}"), Color::Bold))
+        .collect();
+    println!("{}", header.join("  ").trim_end());
+
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|((cell, color), &w)| {
+                let padded = format!("{cell:w$}");
+                match color {
+                    Some(color) => paint(&padded, *color),
+                    None => padded,
+                }
+            })
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+}
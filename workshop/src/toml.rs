@@ -0,0 +1,656 @@
+// A small parser for the subset of TOML that we need: the workspace and crate
+// manifests, plus the workshop's own files. It understands tables, arrays of
+// tables, strings (basic, literal and multi-line), integers, booleans, arrays
+// and inline tables. Floats and dates aren't used anywhere, so they're left
+// out. There's also a writer, for the files the workshop keeps its state in.
+// (Why not the toml crate? See workshop/Cargo.toml.)
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+pub type Table = BTreeMap<String, Value>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    Array(Vec<Value>),
+    Table(Table),
+}
+
+impl Value {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Value::Integer(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_table(&self) -> Option<&Table> {
+        match self {
+            Value::Table(table) => Some(table),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseError {
+    line: usize,
+    message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+pub fn parse(input: &str) -> Result<Table, ParseError> {
+    let mut parser = Parser {
+        chars: input.chars().collect(),
+        pos: 0,
+        line: 1,
+    };
+    parser.document()
+}
+
+pub fn write(table: &Table) -> String {
+    let mut out = String::new();
+    write_table(&mut out, &[], table);
+    out
+}
+
+fn write_table(out: &mut String, path: &[&str], table: &Table) {
+    for (key, value) in table {
+        if !matches!(value, Value::Table(_)) {
+            out.push_str(&format!("{} = {}\n", write_key(key), write_value(value)));
+        }
+    }
+
+    for (key, value) in table {
+        if let Value::Table(inner) = value {
+            let mut path = path.to_vec();
+            path.push(key);
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            let header: Vec<String> = path.iter().map(|k| write_key(k)).collect();
+            out.push_str(&format!("[{}]\n", header.join(".")));
+            write_table(out, &path, inner);
+        }
+    }
+}
+
+fn write_key(key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        key.to_string()
+    } else {
+        write_value(&Value::String(key.to_string()))
+    }
+}
+
+fn write_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => {
+            let mut out = String::from('"');
+            for c in s.chars() {
+                match c {
+                    '"' => out.push_str("\\\""),
+                    '\\' => out.push_str("\\\\"),
+                    '\n' => out.push_str("\\n"),
+                    '\t' => out.push_str("\\t"),
+                    '\r' => out.push_str("\\r"),
+                    c if c.is_control() => out.push_str(&format!("\\u{:04X}", c as u32)),
+                    c => out.push(c),
+                }
+            }
+            out.push('"');
+            out
+        }
+        Value::Integer(i) => i.to_string(),
+        Value::Boolean(b) => b.to_string(),
+        Value::Array(values) => {
+            let values: Vec<String> = values.iter().map(write_value).collect();
+            format!("[{}]", values.join(", "))
+        }
+        Value::Table(table) => {
+            let pairs: Vec<String> = table
+                .iter()
+                .map(|(k, v)| format!("{} = {}", write_key(k), write_value(v)))
+                .collect();
+            format!("{{ {} }}", pairs.join(", "))
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+}
+
+impl Parser {
+    fn document(&mut self) -> Result<Table, ParseError> {
+        let mut root = Table::new();
+        let mut current: Vec<String> = Vec::new();
+
+        loop {
+            self.skip_trivia();
+            match self.peek() {
+                None => break,
+                Some('[') => {
+                    self.bump();
+                    let is_array = self.eat('[');
+                    self.skip_spaces();
+                    let path = self.key_path()?;
+                    self.skip_spaces();
+                    self.expect(']')?;
+                    if is_array {
+                        self.expect(']')?;
+                    }
+                    self.end_of_line()?;
+
+                    let (last, parent) = path.split_last().unwrap();
+                    let parent = self.navigate(&mut root, parent)?;
+                    if is_array {
+                        let entry = parent
+                            .entry(last.clone())
+                            .or_insert_with(|| Value::Array(Vec::new()));
+                        match entry {
+                            Value::Array(tables) => tables.push(Value::Table(Table::new())),
+                            _ => {
+                                return Err(
+                                    self.error(format!("`{last}` is not an array of tables"))
+                                )
+                            }
+                        }
+                    } else {
+                        match parent.get(last) {
+                            None => {
+                                parent.insert(last.clone(), Value::Table(Table::new()));
+                            }
+                            Some(Value::Table(_)) => {}
+                            Some(_) => return Err(self.error(format!("`{last}` is not a table"))),
+                        }
+                    }
+                    current = path;
+                }
+                Some(_) => {
+                    let path = self.key_path()?;
+                    self.skip_spaces();
+                    self.expect('=')?;
+                    self.skip_spaces();
+                    let value = self.value()?;
+
+                    let mut full = current.clone();
+                    full.extend(path);
+                    let (last, parent) = full.split_last().unwrap();
+                    let table = self.navigate(&mut root, parent)?;
+                    if table.insert(last.clone(), value).is_some() {
+                        return Err(self.error(format!("duplicate key `{last}`")));
+                    }
+                    self.end_of_line()?;
+                }
+            }
+        }
+
+        Ok(root)
+    }
+
+    // Walks down `path` from `root`, creating tables as needed. Arrays of tables
+    // resolve to their most recently added element.
+    fn navigate<'a>(
+        &self,
+        root: &'a mut Table,
+        path: &[String],
+    ) -> Result<&'a mut Table, ParseError> {
+        let mut table = root;
+        for key in path {
+            let entry = table
+                .entry(key.clone())
+                .or_insert_with(|| Value::Table(Table::new()));
+            table = match entry {
+                Value::Table(inner) => inner,
+                Value::Array(values) => match values.last_mut() {
+                    Some(Value::Table(inner)) => inner,
+                    _ => return Err(self.error(format!("`{key}` is not a table"))),
+                },
+                _ => return Err(self.error(format!("`{key}` is not a table"))),
+            };
+        }
+        Ok(table)
+    }
+
+    fn key_path(&mut self) -> Result<Vec<String>, ParseError> {
+        let mut path = vec![self.key()?];
+        loop {
+            self.skip_spaces();
+            if !self.eat('.') {
+                return Ok(path);
+            }
+            self.skip_spaces();
+            path.push(self.key()?);
+        }
+    }
+
+    fn key(&mut self) -> Result<String, ParseError> {
+        match self.peek() {
+            Some('"') => {
+                self.bump();
+                self.basic_string()
+            }
+            Some('\'') => {
+                self.bump();
+                self.literal_string()
+            }
+            _ => {
+                let start = self.pos;
+                while matches!(self.peek(), Some(c) if c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                {
+                    self.bump();
+                }
+                if start == self.pos {
+                    return Err(self.error("expected a key".to_string()));
+                }
+                Ok(self.chars[start..self.pos].iter().collect())
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, ParseError> {
+        match self.peek() {
+            Some('"') => {
+                self.bump();
+                if self.eat_str("\"\"") {
+                    self.multiline_basic_string().map(Value::String)
+                } else {
+                    self.basic_string().map(Value::String)
+                }
+            }
+            Some('\'') => {
+                self.bump();
+                if self.eat_str("''") {
+                    self.multiline_literal_string().map(Value::String)
+                } else {
+                    self.literal_string().map(Value::String)
+                }
+            }
+            Some('[') => {
+                self.bump();
+                self.array()
+            }
+            Some('{') => {
+                self.bump();
+                self.inline_table()
+            }
+            Some('t') if self.eat_str("true") => Ok(Value::Boolean(true)),
+            Some('f') if self.eat_str("false") => Ok(Value::Boolean(false)),
+            Some(c) if c.is_ascii_digit() || c == '+' || c == '-' => self.integer(),
+            _ => Err(self.error("expected a value".to_string())),
+        }
+    }
+
+    fn integer(&mut self) -> Result<Value, ParseError> {
+        let start = self.pos;
+        if matches!(self.peek(), Some('+' | '-')) {
+            self.bump();
+        }
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit() || c == '_') {
+            self.bump();
+        }
+        let text: String = self.chars[start..self.pos]
+            .iter()
+            .filter(|&&c| c != '_')
+            .collect();
+        text.parse()
+            .map(Value::Integer)
+            .map_err(|_| self.error(format!("invalid integer `{text}`")))
+    }
+
+    fn array(&mut self) -> Result<Value, ParseError> {
+        let mut values = Vec::new();
+        loop {
+            self.skip_trivia();
+            if self.eat(']') {
+                return Ok(Value::Array(values));
+            }
+            values.push(self.value()?);
+            self.skip_trivia();
+            if !self.eat(',') {
+                self.skip_trivia();
+                self.expect(']')?;
+                return Ok(Value::Array(values));
+            }
+        }
+    }
+
+    fn inline_table(&mut self) -> Result<Value, ParseError> {
+        let mut table = Table::new();
+        self.skip_spaces();
+        if self.eat('}') {
+            return Ok(Value::Table(table));
+        }
+        loop {
+            self.skip_spaces();
+            let path = self.key_path()?;
+            self.skip_spaces();
+            self.expect('=')?;
+            self.skip_spaces();
+            let value = self.value()?;
+            let (last, parent) = path.split_last().unwrap();
+            if self
+                .navigate(&mut table, parent)?
+                .insert(last.clone(), value)
+                .is_some()
+            {
+                return Err(self.error(format!("duplicate key `{last}`")));
+            }
+            self.skip_spaces();
+            if self.eat('}') {
+                return Ok(Value::Table(table));
+            }
+            self.expect(',')?;
+        }
+    }
+
+    fn basic_string(&mut self) -> Result<String, ParseError> {
+        let mut out = String::new();
+        loop {
+            match self.bump() {
+                None | Some('\n') => return Err(self.error("unterminated string".to_string())),
+                Some('"') => return Ok(out),
+                Some('\\') => out.push(self.escape()?),
+                Some(c) => out.push(c),
+            }
+        }
+    }
+
+    fn multiline_basic_string(&mut self) -> Result<String, ParseError> {
+        self.eat('\n');
+        let mut out = String::new();
+        loop {
+            if self.eat_str("\"\"\"") {
+                // Up to two quotes may sit right before the closing delimiter.
+                while self.eat('"') {
+                    out.push('"');
+                }
+                return Ok(out);
+            }
+            match self.bump() {
+                None => return Err(self.error("unterminated string".to_string())),
+                Some('\\') if matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) => {
+                    // A line-ending backslash trims all the whitespace after it.
+                    while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+                        self.bump();
+                    }
+                }
+                Some('\\') => out.push(self.escape()?),
+                Some(c) => out.push(c),
+            }
+        }
+    }
+
+    fn literal_string(&mut self) -> Result<String, ParseError> {
+        let mut out = String::new();
+        loop {
+            match self.bump() {
+                None | Some('\n') => return Err(self.error("unterminated string".to_string())),
+                Some('\'') => return Ok(out),
+                Some(c) => out.push(c),
+            }
+        }
+    }
+
+    fn multiline_literal_string(&mut self) -> Result<String, ParseError> {
+        self.eat('\n');
+        let mut out = String::new();
+        loop {
+            if self.eat_str("'''") {
+                while self.eat('\'') {
+                    out.push('\'');
+                }
+                return Ok(out);
+            }
+            match self.bump() {
+                None => return Err(self.error("unterminated string".to_string())),
+                Some(c) => out.push(c),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Result<char, ParseError> {
+        let c = match self.bump() {
+            Some('b') => '\u{8}',
+            Some('t') => '\t',
+            Some('n') => '\n',
+            Some('f') => '\u{c}',
+            Some('r') => '\r',
+            Some('"') => '"',
+            Some('\\') => '\\',
+            Some(u @ ('u' | 'U')) => {
+                let len = if u == 'u' { 4 } else { 8 };
+                let digits: String = (0..len).filter_map(|_| self.bump()).collect();
+                u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| self.error(format!("invalid unicode escape `\\{u}{digits}`")))?
+            }
+            Some(c) => return Err(self.error(format!("invalid escape `\\{c}`"))),
+            None => return Err(self.error("unterminated string".to_string())),
+        };
+        Ok(c)
+    }
+
+    fn end_of_line(&mut self) -> Result<(), ParseError> {
+        self.skip_spaces();
+        if self.peek() == Some('#') {
+            self.skip_comment();
+        }
+        match self.peek() {
+            None => Ok(()),
+            Some('\n') => {
+                self.bump();
+                Ok(())
+            }
+            Some(c) => Err(self.error(format!("unexpected `{c}`"))),
+        }
+    }
+
+    // Skips whitespace, newlines and comments.
+    fn skip_trivia(&mut self) {
+        loop {
+            match self.peek() {
+                Some(' ' | '\t' | '\r' | '\n') => {
+                    self.bump();
+                }
+                Some('#') => self.skip_comment(),
+                _ => return,
+            }
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\r')) {
+            self.bump();
+        }
+    }
+
+    fn skip_comment(&mut self) {
+        while !matches!(self.peek(), None | Some('\n')) {
+            self.bump();
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_str(&mut self, s: &str) -> bool {
+        let len = s.chars().count();
+        let matches = self.pos + len <= self.chars.len()
+            && self.chars[self.pos..self.pos + len]
+                .iter()
+                .copied()
+                .eq(s.chars());
+        if matches {
+            for _ in 0..len {
+                self.bump();
+            }
+        }
+        matches
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ParseError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{c}`")))
+        }
+    }
+
+    fn error(&self, message: String) -> ParseError {
+        ParseError {
+            line: self.line,
+            message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_workspace_manifest() {
+        let table = parse(
+            r#"
+[workspace]
+
+members = [
+    "lessons/*/",
+    "exercises/*/", # trailing comments are fine
+]
+
+exclude = ["lessons/1-hello-world"]
+"#,
+        )
+        .unwrap();
+
+        let workspace = table["workspace"].as_table().unwrap();
+        let members: Vec<_> = workspace["members"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m.as_str().unwrap())
+            .collect();
+        assert_eq!(members, ["lessons/*/", "exercises/*/"]);
+        assert_eq!(workspace["exclude"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn parse_arrays_of_tables() {
+        let table = parse(
+            r#"
+[package]
+name = "fibonacci"
+
+[[bin]]
+name = "fibonacci-solution"
+path = 'src/solution.rs'
+
+[[bin]]
+name = "other"
+test = false
+"#,
+        )
+        .unwrap();
+
+        let bins = table["bin"].as_array().unwrap();
+        assert_eq!(bins.len(), 2);
+        assert_eq!(
+            bins[0].as_table().unwrap()["path"].as_str(),
+            Some("src/solution.rs")
+        );
+        assert_eq!(bins[1].as_table().unwrap()["test"], Value::Boolean(false));
+    }
+
+    #[test]
+    fn parse_strings_and_integers() {
+        let table = parse(
+            "a = \"tab\\there\"\nb = \"\"\"\nline one\nline two\"\"\"\nc = -1_000\nd = { x = 1, y = 'z' }\n",
+        )
+        .unwrap();
+
+        assert_eq!(table["a"].as_str(), Some("tab\there"));
+        assert_eq!(table["b"].as_str(), Some("line one\nline two"));
+        assert_eq!(table["c"], Value::Integer(-1000));
+        assert_eq!(table["d"].as_table().unwrap()["y"].as_str(), Some("z"));
+    }
+
+    #[test]
+    fn write_round_trips() {
+        let text = "\
+version = 1
+
+[hints]
+fibonacci = 2
+\"odd key\" = [\"a \\\"quoted\\\" line\\n\", true]
+";
+        let table = parse(text).unwrap();
+        assert_eq!(write(&table), text);
+    }
+
+    #[test]
+    fn reject_duplicate_keys() {
+        let err = parse("a = 1\na = 2\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: duplicate key `a`");
+    }
+}
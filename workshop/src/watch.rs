@@ -0,0 +1,193 @@
+// Watch mode: grade everything once, then keep an eye on the exercise sources
+// and re-grade an exercise whenever one of its files changes. We poll the file
+// modification times rather than rely on inotify and friends, so this works on
+// any machine (and inside any container) without extra services.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::args::Args;
+use crate::grade::{self, Outcome};
+use crate::manifest::{Exercise, Manifest};
+use crate::progress;
+use crate::term::{self, Color};
+use crate::workspace::Workspace;
+use crate::Result;
+
+// How many lines of a compiler error or failed assertion we show at once
+const MAX_PROBLEM_LINES: usize = 20;
+
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+pub fn command(workspace: &Workspace, args: &[String]) -> Result<ExitCode> {
+    let args = Args::parse(args, &[], &[("--interval", "a value in milliseconds")])?;
+    let interval = match args.value("--interval") {
+        Some(millis) => Duration::from_millis(
+            millis
+                .parse()
+                .map_err(|_| format!("invalid interval `{millis}`, expected milliseconds"))?,
+        ),
+        None => Duration::from_millis(500),
+    };
+    let names: Vec<String> = args.positional().into_iter().map(String::from).collect();
+
+    let exercises = grade::select(Manifest::load(workspace)?, &names)?;
+    let mut snapshot_errors: Vec<Option<String>> = vec![None; exercises.len()];
+    let mut snapshots = Vec::new();
+    let mut outcomes = Vec::new();
+    for (i, exercise) in exercises.iter().enumerate() {
+        // Without a snapshot, the first one that works counts as a change.
+        snapshots.push(check_files(exercise, &mut snapshot_errors[i]).unwrap_or_default());
+        println!(
+            "{} {}...",
+            term::paint("Grading", Color::Cyan),
+            exercise.name
+        );
+        outcomes.push(grade_and_record(workspace, exercise));
+    }
+
+    // Start by showing the first exercise that still needs work.
+    let focus = outcomes.iter().position(|o| !o.passed).unwrap_or(0);
+    render(workspace, &exercises, &outcomes, focus);
+
+    loop {
+        thread::sleep(interval);
+        for (i, exercise) in exercises.iter().enumerate() {
+            let Some(current) = check_files(exercise, &mut snapshot_errors[i]) else {
+                continue;
+            };
+            if current == snapshots[i] {
+                continue;
+            }
+            snapshots[i] = current;
+
+            println!(
+                "\n{} {}...",
+                term::paint("Re-grading", Color::Cyan),
+                exercise.name
+            );
+            outcomes[i] = grade_and_record(workspace, exercise);
+            render(workspace, &exercises, &outcomes, i);
+        }
+    }
+}
+
+// The exercise's snapshot, or None if it can't be taken right now, like in the
+// middle of an editor saving a file. `last_error` is what went wrong last
+// time, so that a problem that lasts is only reported once.
+fn check_files(exercise: &Exercise, last_error: &mut Option<String>) -> Option<Snapshot> {
+    match snapshot(&exercise.dir) {
+        Ok(current) => {
+            *last_error = None;
+            Some(current)
+        }
+        Err(err) => {
+            let err = err.to_string();
+            if last_error.as_ref() != Some(&err) {
+                eprintln!(
+                    "{} couldn't look for changes to {}: {err}",
+                    term::paint("warning:", Color::Red),
+                    exercise.name
+                );
+                *last_error = Some(err);
+            }
+            None
+        }
+    }
+}
+
+// Grades the exercise and records how it went. When grading itself fails, like
+// if cargo couldn't be started, the exercise fails with that error as its
+// output, and saving again tries again.
+fn grade_and_record(workspace: &Workspace, exercise: &Exercise) -> Outcome {
+    let outcome = grade::grade(workspace, exercise).unwrap_or_else(|err| Outcome {
+        passed: false,
+        output: format!("error: failed to grade {}: {err}\n", exercise.name),
+        mismatch: None,
+        elapsed: Duration::ZERO,
+    });
+    if let Err(err) = progress::record(workspace, &exercise.name, outcome.passed) {
+        eprintln!(
+            "{} failed to record your progress: {err}",
+            term::paint("warning:", Color::Red)
+        );
+    }
+    outcome
+}
+
+fn render(workspace: &Workspace, exercises: &[Exercise], outcomes: &[Outcome], focus: usize) {
+    if term::use_color() {
+        // Clear the screen and move the cursor to the top left.
+        print!("\x1b[2J\x1b[H");
+    }
+
+    let passed = outcomes.iter().filter(|o| o.passed).count();
+    println!(
+        "{} {passed}/{} exercises passed (Ctrl-C to quit)\n",
+        term::paint("workshop watch:", Color::Bold),
+        exercises.len()
+    );
+
+    for (i, (exercise, outcome)) in exercises.iter().zip(outcomes).enumerate() {
+        let marker = if i == focus { ">" } else { " " };
+        let result = if outcome.passed {
+            term::paint("PASS", Color::Green)
+        } else {
+            term::paint("FAIL", Color::Red)
+        };
+        println!("{marker} {result}  {}", exercise.name);
+    }
+    println!();
+
+    let (exercise, outcome) = (&exercises[focus], &outcomes[focus]);
+    if outcome.passed {
+        println!("{} is done, nice work!", exercise.name);
+        return;
+    }
+
+    match outcome.first_problem(term::use_color()) {
+        Some(problem) => {
+            let lines: Vec<&str> = problem.lines().collect();
+            for line in lines.iter().take(MAX_PROBLEM_LINES) {
+                println!("{line}");
+            }
+            if lines.len() > MAX_PROBLEM_LINES {
+                println!("{}", term::paint("...", Color::Dim));
+            }
+        }
+        None => println!("{} failed, but didn't say why.", exercise.name),
+    }
+    let dir = exercise
+        .dir
+        .strip_prefix(&workspace.root)
+        .unwrap_or(&exercise.dir);
+    let note = format!("Save a file in {} to re-grade it.", dir.display());
+    println!("\n{}", term::paint(&note, Color::Dim));
+}
+
+// The modification time of every file that can affect an exercise's grade.
+fn snapshot(dir: &Path) -> io::Result<Snapshot> {
+    let mut files = Snapshot::new();
+    let manifest = dir.join("Cargo.toml");
+    files.insert(manifest.clone(), fs::metadata(&manifest)?.modified()?);
+    visit(&dir.join("src"), &mut files)?;
+    Ok(files)
+}
+
+fn visit(dir: &Path, files: &mut Snapshot) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            visit(&entry.path(), files)?;
+        } else {
+            files.insert(entry.path(), metadata.modified()?);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,209 @@
+// Everything we need to know about the cargo workspace: where its root is, which
+// crates are members, and what binaries those crates have.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::toml::{self, Table};
+use crate::Result;
+
+pub struct Workspace {
+    pub root: PathBuf,
+    // Member directories relative to the root, in sorted order
+    pub members: Vec<PathBuf>,
+}
+
+impl Workspace {
+    // Finds the workspace containing `start` by walking up until we hit a
+    // Cargo.toml with a [workspace] table.
+    pub fn discover(start: &Path) -> Result<Workspace> {
+        for dir in start.ancestors() {
+            let manifest = dir.join("Cargo.toml");
+            if manifest.is_file() && read_manifest(&manifest)?.contains_key("workspace") {
+                return Workspace::load(dir);
+            }
+        }
+        Err(format!(
+            "could not find a workspace Cargo.toml above {}",
+            start.display()
+        )
+        .into())
+    }
+
+    // The workshop's own workspace, for tests that use the real lessons and
+    // exercises
+    #[cfg(test)]
+    pub fn ours() -> Workspace {
+        Workspace::discover(Path::new(env!("CARGO_MANIFEST_DIR"))).unwrap()
+    }
+
+    pub fn load(root: &Path) -> Result<Workspace> {
+        let manifest = read_manifest(&root.join("Cargo.toml"))?;
+        let workspace = manifest
+            .get("workspace")
+            .and_then(|w| w.as_table())
+            .ok_or("Cargo.toml has no [workspace] table")?;
+
+        let exclude: Vec<PathBuf> = string_list(workspace, "exclude")?
+            .iter()
+            .map(|path| normalize(path))
+            .collect();
+
+        let mut members = Vec::new();
+        for pattern in string_list(workspace, "members")? {
+            for member in expand(root, &pattern)? {
+                if !exclude.contains(&member) && !members.contains(&member) {
+                    members.push(member);
+                }
+            }
+        }
+        members.sort();
+
+        Ok(Workspace {
+            root: root.to_path_buf(),
+            members,
+        })
+    }
+}
+
+pub struct Crate {
+    pub name: String,
+    // Names of the binary targets, including the implicit one for src/main.rs
+    pub bins: Vec<String>,
+}
+
+impl Crate {
+    pub fn load(dir: &Path) -> Result<Crate> {
+        let manifest = read_manifest(&dir.join("Cargo.toml"))?;
+        let name = manifest
+            .get("package")
+            .and_then(|p| p.as_table())
+            .and_then(|p| p.get("name"))
+            .and_then(|n| n.as_str())
+            .ok_or_else(|| format!("{} has no package name", dir.join("Cargo.toml").display()))?
+            .to_string();
+
+        let mut bins = Vec::new();
+        if dir.join("src/main.rs").is_file() {
+            bins.push(name.clone());
+        }
+        for bin in manifest
+            .get("bin")
+            .and_then(|b| b.as_array())
+            .unwrap_or_default()
+        {
+            let bin = bin
+                .as_table()
+                .and_then(|b| b.get("name"))
+                .and_then(|n| n.as_str())
+                .ok_or_else(|| format!("a [[bin]] of `{name}` has no name"))?;
+            if !bins.iter().any(|b| b == bin) {
+                bins.push(bin.to_string());
+            }
+        }
+
+        Ok(Crate { name, bins })
+    }
+}
+
+fn read_manifest(path: &Path) -> Result<Table> {
+    let text = fs::read_to_string(path)
+        .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+    toml::parse(&text).map_err(|err| format!("failed to parse {}: {err}", path.display()).into())
+}
+
+fn string_list(table: &Table, key: &str) -> Result<Vec<String>> {
+    let Some(value) = table.get(key) else {
+        return Ok(Vec::new());
+    };
+    value
+        .as_array()
+        .and_then(|values| {
+            values
+                .iter()
+                .map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .ok_or_else(|| format!("`workspace.{key}` must be a list of strings").into())
+}
+
+// Strips "./" and trailing slashes so "lessons/*/" and "lessons/*" compare equal.
+fn normalize(path: &str) -> PathBuf {
+    Path::new(path).components().collect()
+}
+
+// Expands a member pattern into the directories it names. Like cargo, we only
+// allow wildcards in the last path component, which covers "exercises/*/".
+fn expand(root: &Path, pattern: &str) -> Result<Vec<PathBuf>> {
+    let pattern = normalize(pattern);
+    let Some(last) = pattern.file_name().and_then(|name| name.to_str()) else {
+        return Ok(vec![pattern]);
+    };
+    if !last.contains(['*', '?']) {
+        return Ok(vec![pattern]);
+    }
+
+    let parent = pattern.parent().unwrap_or(Path::new(""));
+    let mut matches = Vec::new();
+    for entry in fs::read_dir(root.join(parent))? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if entry.file_type()?.is_dir() && glob_match(last, &name.to_string_lossy()) {
+            matches.push(parent.join(name));
+        }
+    }
+    Ok(matches)
+}
+
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    fn matches(pattern: &[char], name: &[char]) -> bool {
+        match (pattern.first(), name.first()) {
+            (None, None) => true,
+            (Some('*'), _) => {
+                matches(&pattern[1..], name) || (!name.is_empty() && matches(pattern, &name[1..]))
+            }
+            (Some('?'), Some(_)) => matches(&pattern[1..], &name[1..]),
+            (Some(p), Some(n)) if p == n => matches(&pattern[1..], &name[1..]),
+            _ => false,
+        }
+    }
+
+    matches(&pattern, &name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_patterns() {
+        assert!(glob_match("*", "fibonacci"));
+        assert!(glob_match("*-hello-*", "1-hello-world"));
+        assert!(glob_match("?-enums", "7-enums"));
+        assert!(!glob_match("?-enums", "10-enums"));
+        assert!(!glob_match("fib*", "packages"));
+    }
+
+    #[test]
+    fn discover_this_workspace() {
+        let workspace = Workspace::ours();
+
+        assert!(workspace
+            .members
+            .contains(&PathBuf::from("exercises/fibonacci")));
+        assert!(workspace.members.contains(&PathBuf::from("workshop")));
+        assert!(!workspace
+            .members
+            .contains(&PathBuf::from("lessons/1-hello-world")));
+
+        let fibonacci = Crate::load(&workspace.root.join("exercises/fibonacci")).unwrap();
+        assert_eq!(fibonacci.name, "fibonacci");
+        assert_eq!(
+            fibonacci.bins,
+            ["fibonacci", "fibonacci-solution", "fibonacci-bench"]
+        );
+    }
+}